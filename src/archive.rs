@@ -0,0 +1,594 @@
+//! This module implements functionality for downloading and unpacking archives of existing
+//! Minecraft servers (e.g., a world backup shared by another server host), and for packing them
+//! up again (see [`TarGzBuilder`]).
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use axiom::archive::{self, Format};
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let url = "https://example.com/world.tar.gz";
+//!     let format = Format::from_name(url).expect("expected a supported archive");
+//!     let download = std::path::Path::new("world.tar.gz");
+//!     archive::fetch(url, std::time::Duration::from_secs(120), download)?;
+//!     let file = std::fs::File::open(download)?;
+//!     archive::extract(std::io::BufReader::new(file), format, std::path::Path::new("server"))?;
+//!     Ok(())
+//! }
+//! ```
+
+use std::io::{Read, Seek, Write};
+use std::path::{Component, Path, PathBuf};
+
+type StdError = dyn std::error::Error + Send + Sync + 'static;
+
+/// Describes the supported archive formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A ZIP archive (`.zip`).
+    Zip,
+    /// A gzip-compressed tar archive (`.tar.gz` or `.tgz`).
+    TarGz,
+}
+
+impl Format {
+    /// Guess the archive format from a file name or URL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use axiom::archive::Format;
+    ///
+    /// # fn main() {
+    /// assert_eq!(Format::from_name("world.zip"), Some(Format::Zip));
+    /// assert_eq!(Format::from_name("https://example.com/world.tar.gz?v=1"), Some(Format::TarGz));
+    /// assert_eq!(Format::from_name("world.rar"), None);
+    /// # }
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name
+            .split(['?', '#'])
+            .next()
+            .unwrap_or(name)
+            .to_ascii_lowercase();
+
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+/// Download the archive at `url` into the file at `destination`.
+///
+/// The response is written to the file as it arrives, so large archives (e.g., worlds) don't
+/// have to fit in memory.
+pub fn fetch(
+    url: &str,
+    timeout: std::time::Duration,
+    destination: &Path,
+) -> Result<(), ArchiveError> {
+    let mut response = crate::paper::client()
+        .get(url)
+        .timeout(timeout)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .map_err(ArchiveError::request_failed)?;
+
+    debug_assert!(response.status().is_success());
+
+    let file = std::fs::File::create(destination)
+        .map_err(|err| ArchiveError::write_failed(destination, err))?;
+    let mut file = std::io::BufWriter::new(file);
+    let mut buffer = vec![0; 64 * 1024];
+
+    loop {
+        let read = response
+            .read(&mut buffer)
+            .map_err(ArchiveError::response_timed_out)?;
+
+        if read == 0 {
+            break;
+        }
+
+        file.write_all(&buffer[..read])
+            .map_err(|err| ArchiveError::write_failed(destination, err))?;
+    }
+
+    file.flush()
+        .map_err(|err| ArchiveError::write_failed(destination, err))?;
+
+    Ok(())
+}
+
+/// Unpack the archive read from `reader` into the `destination` directory.
+///
+/// Entries are unpacked one at a time, so the archive is never held in memory all at once.
+/// Entries that would be written outside of `destination` (absolute paths, or paths containing
+/// `..`) cause the whole archive to be rejected. Links are skipped, since they could be used to
+/// point outside of `destination` too.
+pub fn extract<R: Read + Seek>(
+    reader: R,
+    format: Format,
+    destination: &Path,
+) -> Result<(), ArchiveError> {
+    match format {
+        Format::Zip => extract_zip(reader, destination),
+        Format::TarGz => extract_tar(flate2::read::GzDecoder::new(reader), destination),
+    }
+}
+
+fn extract_zip<R: Read + Seek>(reader: R, destination: &Path) -> Result<(), ArchiveError> {
+    let mut archive = zip::ZipArchive::new(reader).map_err(ArchiveError::invalid_archive)?;
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).map_err(ArchiveError::invalid_archive)?;
+        let path = safe_path(destination, file.name())?;
+
+        if file.is_dir() {
+            create_dir(&path)?;
+            continue;
+        }
+
+        if file.is_symlink() {
+            continue;
+        }
+
+        let mode = file.unix_mode();
+        write_file(&path, &mut file, mode)?;
+    }
+
+    Ok(())
+}
+
+fn extract_tar(reader: impl Read, destination: &Path) -> Result<(), ArchiveError> {
+    let mut archive = tar::Archive::new(reader);
+    let entries = archive.entries().map_err(ArchiveError::invalid_archive)?;
+
+    for entry in entries {
+        let mut entry = entry.map_err(ArchiveError::invalid_archive)?;
+        let name = String::from_utf8_lossy(&entry.path_bytes()).into_owned();
+
+        match entry.header().entry_type() {
+            tar::EntryType::Regular | tar::EntryType::Continuous => {
+                let path = safe_path(destination, &name)?;
+                let mode = entry.header().mode().ok();
+                write_file(&path, &mut entry, mode)?;
+            }
+            tar::EntryType::Directory => create_dir(&safe_path(destination, &name)?)?,
+            // Links, devices, global PAX headers, etc.
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Get the names of the entries in the archive read from `reader`, in the order they appear in
+/// the archive.
+///
+/// Directories are included, and their names always end with a `/`.
+pub fn list<R: Read + Seek>(reader: R, format: Format) -> Result<Vec<String>, ArchiveError> {
+    match format {
+        Format::Zip => {
+            let archive = zip::ZipArchive::new(reader).map_err(ArchiveError::invalid_archive)?;
+            Ok(archive.file_names().map(str::to_owned).collect())
+        }
+        Format::TarGz => {
+            let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(reader));
+            let entries = archive.entries().map_err(ArchiveError::invalid_archive)?;
+            let mut names = Vec::new();
+
+            for entry in entries {
+                let entry = entry.map_err(ArchiveError::invalid_archive)?;
+                let mut name = String::from_utf8_lossy(&entry.path_bytes()).into_owned();
+
+                if entry.header().entry_type().is_dir() && !name.ends_with('/') {
+                    name.push('/');
+                }
+
+                names.push(name);
+            }
+
+            Ok(names)
+        }
+    }
+}
+
+/// Packs files into a gzip-compressed tar archive, which can be unpacked again with [`extract`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use axiom::archive::TarGzBuilder;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let file = std::fs::File::create("example.tar.gz")?;
+///     let mut builder = TarGzBuilder::new(file);
+///     builder.append_dir("example")?;
+///     builder.append_file("example/Axiom.toml", std::path::Path::new("Axiom.toml"))?;
+///     builder.finish()?;
+///     Ok(())
+/// }
+/// ```
+pub struct TarGzBuilder<W: Write> {
+    builder: tar::Builder<flate2::write::GzEncoder<W>>,
+}
+
+impl<W: Write> TarGzBuilder<W> {
+    /// Start a new archive, which is written to `writer`.
+    pub fn new(writer: W) -> Self {
+        let encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+
+        Self {
+            builder: tar::Builder::new(encoder),
+        }
+    }
+
+    /// Add an empty directory called `name` to the archive.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - There is a problem writing to the archive.
+    pub fn append_dir(&mut self, name: &str) -> std::io::Result<()> {
+        let mut header = entry_header(tar::EntryType::Directory, 0o755, 0, 0);
+        self.builder
+            .append_data(&mut header, name, std::io::empty())
+    }
+
+    /// Add the contents of the file at `path` to the archive, as an entry called `name`.
+    ///
+    /// Symbolic links are followed, so the archive contains the file they point to. Files of any
+    /// size are supported; sizes that don't fit in the tar header are stored as GNU extensions.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - There is a problem reading the file.
+    /// - There is a problem writing to the archive.
+    pub fn append_file(&mut self, name: &str, path: &Path) -> std::io::Result<()> {
+        let file = std::fs::File::open(path)?;
+        let metadata = file.metadata()?;
+
+        #[cfg(unix)]
+        let mode = std::os::unix::fs::PermissionsExt::mode(&metadata.permissions()) & 0o777;
+        #[cfg(not(unix))]
+        let mode = 0o644;
+
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |modified| modified.as_secs());
+
+        let mut header = entry_header(tar::EntryType::Regular, mode, metadata.len(), modified);
+
+        // Copy exactly the size in the header, even if the file changes while it is being read.
+        let contents = ExactReader {
+            inner: file,
+            remaining: metadata.len(),
+            path,
+        };
+        self.builder.append_data(&mut header, name, contents)
+    }
+
+    /// Finish the archive, returning the writer it was written to.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - There is a problem writing to the archive.
+    pub fn finish(self) -> std::io::Result<W> {
+        self.builder.into_inner()?.finish()
+    }
+}
+
+/// Create the header for an entry in a [`TarGzBuilder`]; the name and checksum are filled in
+/// when the entry is added.
+fn entry_header(kind: tar::EntryType, mode: u32, size: u64, modified: u64) -> tar::Header {
+    // GNU headers can store long names, and sizes of 8 GiB or more.
+    let mut header = tar::Header::new_gnu();
+    header.set_entry_type(kind);
+    header.set_mode(mode);
+    header.set_size(size);
+    header.set_mtime(modified);
+    header
+}
+
+/// Reads exactly `remaining` bytes from `inner`, failing if it runs out of bytes before that.
+struct ExactReader<'a, R> {
+    inner: R,
+    remaining: u64,
+    /// The file being read, for the error message.
+    path: &'a Path,
+}
+
+impl<R: Read> Read for ExactReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.remaining == 0 {
+            return Ok(0);
+        }
+
+        let read = (&mut self.inner).take(self.remaining).read(buf)?;
+
+        if read == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!(
+                    "{} got shorter while it was being read",
+                    self.path.display()
+                ),
+            ));
+        }
+
+        self.remaining -= read as u64;
+        Ok(read)
+    }
+}
+
+/// Resolve `name` (from an archive) relative to `destination`, making sure it stays inside.
+fn safe_path(destination: &Path, name: &str) -> Result<PathBuf, ArchiveError> {
+    let mut path = destination.to_path_buf();
+
+    for component in Path::new(name).components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(ArchiveError::UnsafePath {
+                    path: name.to_owned(),
+                });
+            }
+        }
+    }
+
+    Ok(path)
+}
+
+fn create_dir(path: &Path) -> Result<(), ArchiveError> {
+    std::fs::create_dir_all(path).map_err(|err| ArchiveError::write_failed(path, err))
+}
+
+fn write_file(path: &Path, contents: &mut dyn Read, mode: Option<u32>) -> Result<(), ArchiveError> {
+    if let Some(parent) = path.parent() {
+        create_dir(parent)?;
+    }
+
+    std::fs::File::create(path)
+        .and_then(|mut file| std::io::copy(contents, &mut file))
+        .map_err(|err| ArchiveError::write_failed(path, err))?;
+
+    // Keep scripts (e.g., `start.sh`) executable.
+    #[cfg(unix)]
+    if let Some(mode) = mode.filter(|mode| mode & 0o111 != 0) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let permissions = std::fs::Permissions::from_mode(mode & 0o777);
+        std::fs::set_permissions(path, permissions)
+            .map_err(|err| ArchiveError::write_failed(path, err))?;
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+
+    Ok(())
+}
+
+/// Represents errors that can occur while downloading or unpacking an archive.
+#[derive(Debug)]
+pub enum ArchiveError {
+    /// An error occurred while attempting to send the download request.
+    RequestFailed {
+        /// The underlying error that caused the request to fail.
+        source: Box<StdError>,
+    },
+    /// The download took too long to complete.
+    ResponseTimedOut {
+        /// The underlying error that caused the timeout.
+        source: Box<StdError>,
+    },
+    /// The archive is corrupted or not in the expected format.
+    InvalidArchive {
+        /// The underlying error that caused the failure.
+        source: Box<StdError>,
+    },
+    /// An entry in the archive would be written outside of the destination directory.
+    UnsafePath {
+        /// The path of the entry, as it appears in the archive.
+        path: String,
+    },
+    /// There was a problem writing an entry to disk.
+    WriteFailed {
+        /// The path that was being written to.
+        path: PathBuf,
+        /// The underlying error that caused the failure.
+        source: std::io::Error,
+    },
+}
+
+impl std::fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RequestFailed { source: _ } => write!(f, "failed to send download request"),
+            Self::ResponseTimedOut { source: _ } => write!(f, "failed to get response body"),
+            Self::InvalidArchive { source: _ } => write!(f, "failed to read archive"),
+            Self::UnsafePath { path } => {
+                write!(
+                    f,
+                    "archive contains a path outside of the server directory: {path}"
+                )
+            }
+            Self::WriteFailed { path, source: _ } => {
+                write!(f, "failed to write to {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArchiveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::RequestFailed { source } => Some(source.as_ref()),
+            Self::ResponseTimedOut { source } => Some(source.as_ref()),
+            Self::InvalidArchive { source } => Some(source.as_ref()),
+            Self::UnsafePath { path: _ } => None,
+            Self::WriteFailed { path: _, source } => Some(source),
+        }
+    }
+}
+
+impl ArchiveError {
+    /// Creates an error indicating that the download request has failed.
+    pub fn request_failed(source: impl Into<Box<StdError>>) -> Self {
+        Self::RequestFailed {
+            source: source.into(),
+        }
+    }
+
+    /// Creates an error indicating that the download has timed out.
+    pub fn response_timed_out(source: impl Into<Box<StdError>>) -> Self {
+        Self::ResponseTimedOut {
+            source: source.into(),
+        }
+    }
+
+    /// Creates an error indicating that the archive could not be read.
+    pub fn invalid_archive(source: impl Into<Box<StdError>>) -> Self {
+        Self::InvalidArchive {
+            source: source.into(),
+        }
+    }
+
+    /// Creates an error indicating a failure to write an entry to `path`.
+    pub fn write_failed(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        Self::WriteFailed {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The size of each header and data block in a tar archive.
+    const TAR_BLOCK_SIZE: usize = 512;
+
+    /// Build a tar header block for an entry called `name`.
+    fn tar_header(name: &str, kind: u8, size: usize) -> Vec<u8> {
+        let mut header = vec![0; TAR_BLOCK_SIZE];
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        header[100..107].copy_from_slice(b"0000644");
+        header[124..135].copy_from_slice(format!("{size:011o}").as_bytes());
+        header[156] = kind;
+        header[257..265].copy_from_slice(b"ustar  \0");
+
+        // The checksum is calculated with the checksum field itself filled with spaces.
+        header[148..156].fill(b' ');
+        let checksum: u32 = header.iter().map(|&byte| u32::from(byte)).sum();
+        header[148..155].copy_from_slice(format!("{checksum:06o}\0").as_bytes());
+        header
+    }
+
+    fn tar(entries: &[(&str, u8, &[u8])]) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        for (name, kind, contents) in entries {
+            data.extend(tar_header(name, *kind, contents.len()));
+            data.extend(*contents);
+            data.resize(data.len().div_ceil(TAR_BLOCK_SIZE) * TAR_BLOCK_SIZE, 0);
+        }
+
+        data.extend([0; TAR_BLOCK_SIZE * 2]);
+        data
+    }
+
+    #[test]
+    fn test_safe_path() {
+        let destination = Path::new("server");
+
+        assert_eq!(
+            safe_path(destination, "world/level.dat").unwrap(),
+            Path::new("server/world/level.dat")
+        );
+        assert_eq!(
+            safe_path(destination, "./server.properties").unwrap(),
+            Path::new("server/server.properties")
+        );
+        assert!(safe_path(destination, "../evil.sh").is_err());
+        assert!(safe_path(destination, "world/../../evil.sh").is_err());
+        assert!(safe_path(destination, "/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_extract_tar() {
+        let directory = tempfile::tempdir().unwrap();
+        let destination = directory.path();
+        let long_name = format!("world/{}.dat", "a".repeat(120));
+
+        let data = tar(&[
+            ("world", b'5', b""),
+            ("server.properties", b'0', b"motd=Hello\n"),
+            ("././@LongLink", b'L', long_name.as_bytes()),
+            ("world/aaaa", b'0', b"long"),
+            ("link", b'2', b""),
+        ]);
+        extract_tar(data.as_slice(), destination).unwrap();
+
+        let properties = std::fs::read_to_string(destination.join("server.properties"));
+        assert_eq!(properties.unwrap(), "motd=Hello\n");
+        assert_eq!(
+            std::fs::read(destination.join(&long_name)).unwrap(),
+            b"long"
+        );
+        assert!(!destination.join("link").exists());
+
+        let data = tar(&[("../evil.sh", b'0', b"echo")]);
+        assert!(matches!(
+            extract_tar(data.as_slice(), destination),
+            Err(ArchiveError::UnsafePath { .. })
+        ));
+    }
+
+    #[test]
+    fn test_tar_gz_builder() {
+        let root = tempfile::tempdir().unwrap();
+        let file = root.path().join("start.sh");
+        std::fs::write(&file, "#!/bin/sh\n").unwrap();
+
+        let long_name = format!("example/{}.dat", "a".repeat(120));
+        let mut builder = TarGzBuilder::new(Vec::new());
+        builder.append_dir("example").unwrap();
+        builder.append_file("example/start.sh", &file).unwrap();
+        builder.append_file(&long_name, &file).unwrap();
+        let data = builder.finish().unwrap();
+
+        assert_eq!(
+            list(std::io::Cursor::new(&data), Format::TarGz).unwrap(),
+            vec!["example/", "example/start.sh", long_name.as_str()]
+        );
+
+        let destination = root.path().join("extracted");
+        extract(std::io::Cursor::new(&data), Format::TarGz, &destination).unwrap();
+        let contents = std::fs::read_to_string(destination.join("example/start.sh"));
+        assert_eq!(contents.unwrap(), "#!/bin/sh\n");
+    }
+
+    #[test]
+    fn test_entry_header_large_size() {
+        // Too big for the 11 octal digits of a plain tar header.
+        let size = 9 << 30;
+        let header = entry_header(tar::EntryType::Regular, 0o644, size, 0);
+
+        assert_eq!(header.size().unwrap(), size);
+        assert_eq!(header.mode().unwrap(), 0o644);
+    }
+}
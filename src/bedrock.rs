@@ -0,0 +1,341 @@
+//! This module implements functionality for pinging Minecraft: Bedrock Edition servers, which use
+//! the RakNet protocol over UDP instead of the Java Edition's Server List Ping.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let timeout = std::time::Duration::from_secs(5);
+//!     let pong = axiom::bedrock::ping("127.0.0.1", None, timeout)?;
+//!     println!("{} ({}/{} players)", pong.motd(), pong.online(), pong.max());
+//!     Ok(())
+//! }
+//! ```
+
+use std::net::ToSocketAddrs;
+
+/// The port Bedrock Edition servers listen on by default.
+pub const DEFAULT_PORT: u16 = 19132;
+
+/// The bytes RakNet uses to recognize offline (unconnected) messages.
+const MAGIC: [u8; 16] = [
+    0x00, 0xFF, 0xFF, 0x00, 0xFE, 0xFE, 0xFE, 0xFE, 0xFD, 0xFD, 0xFD, 0xFD, 0x12, 0x34, 0x56, 0x78,
+];
+
+/// The ID of the Unconnected Ping packet.
+const UNCONNECTED_PING: u8 = 0x01;
+
+/// The ID of the Unconnected Pong packet.
+const UNCONNECTED_PONG: u8 = 0x1C;
+
+/// Describes the server information sent in response to an unconnected ping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pong {
+    edition: String,
+    motd: String,
+    sub_motd: Option<String>,
+    protocol: i32,
+    version: String,
+    online: u32,
+    max: u32,
+    game_mode: Option<String>,
+}
+
+impl Pong {
+    /// The edition of the game (`MCPE` for Bedrock Edition, or `MCEE` for Education Edition).
+    pub fn edition(&self) -> &str {
+        &self.edition
+    }
+
+    /// The first line of the server's Message of the Day.
+    pub fn motd(&self) -> &str {
+        &self.motd
+    }
+
+    /// The second line of the server's Message of the Day (usually the name of the world).
+    pub fn sub_motd(&self) -> Option<&str> {
+        self.sub_motd.as_deref()
+    }
+
+    /// The network protocol version the server is using.
+    pub fn protocol(&self) -> i32 {
+        self.protocol
+    }
+
+    /// The version of the game the server is running (e.g., `1.21.90`).
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// The number of players currently connected to the server.
+    pub fn online(&self) -> u32 {
+        self.online
+    }
+
+    /// The maximum number of players that can connect to the server.
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+
+    /// The default game mode of the server (e.g., `Survival`).
+    pub fn game_mode(&self) -> Option<&str> {
+        self.game_mode.as_deref()
+    }
+}
+
+/// Ping the Bedrock Edition server at `hostname` to get basic information about it.
+///
+/// If `port` is `None`, [`DEFAULT_PORT`] is used.
+///
+/// # Errors
+///
+/// This function returns an error if:
+///
+/// - The address can't be resolved.
+/// - The server doesn't respond within `timeout`.
+/// - The response is not a valid Unconnected Pong packet.
+pub fn ping(
+    hostname: &str,
+    port: Option<u16>,
+    timeout: std::time::Duration,
+) -> Result<Pong, BedrockError> {
+    let port = port.unwrap_or(DEFAULT_PORT);
+    let address = (hostname, port)
+        .to_socket_addrs()
+        .map_err(BedrockError::resolve_failed)?
+        .next()
+        .ok_or_else(|| BedrockError::resolve_failed("no addresses found"))?;
+
+    let local: std::net::SocketAddr = match address {
+        std::net::SocketAddr::V4(_) => ([0, 0, 0, 0], 0).into(),
+        std::net::SocketAddr::V6(_) => ([0u16; 8], 0).into(),
+    };
+
+    let socket = std::net::UdpSocket::bind(local).map_err(BedrockError::io_failed)?;
+    socket
+        .set_read_timeout(Some(timeout))
+        .map_err(BedrockError::io_failed)?;
+    socket
+        .send_to(&create_ping_packet(), address)
+        .map_err(BedrockError::io_failed)?;
+
+    // RakNet packets are limited by the MTU, so this is plenty.
+    let mut buffer = [0u8; 2048];
+    let length = match socket.recv(&mut buffer) {
+        Ok(length) => length,
+        Err(err)
+            if matches!(
+                err.kind(),
+                std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+            ) =>
+        {
+            return Err(BedrockError::TimedOut);
+        }
+        Err(err) => return Err(BedrockError::io_failed(err)),
+    };
+
+    parse_pong_packet(&buffer[..length])
+}
+
+/// Construct the Unconnected Ping packet.
+///
+/// https://minecraft.wiki/w/RakNet#Unconnected_Ping
+fn create_ping_packet() -> Vec<u8> {
+    let time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as i64)
+        .unwrap_or_default();
+    // The GUID only needs to be unique enough to tell clients apart.
+    let client_guid = (i64::from(std::process::id()) << 32) ^ time;
+
+    let mut packet = Vec::with_capacity(1 + 8 + MAGIC.len() + 8);
+    packet.push(UNCONNECTED_PING);
+    packet.extend(time.to_be_bytes());
+    packet.extend(MAGIC);
+    packet.extend(client_guid.to_be_bytes());
+
+    packet
+}
+
+/// Parse the Unconnected Pong packet sent by the server.
+///
+/// https://minecraft.wiki/w/RakNet#Unconnected_Pong
+fn parse_pong_packet(packet: &[u8]) -> Result<Pong, BedrockError> {
+    // Packet ID (1 byte) + time (8 bytes) + server GUID (8 bytes) + magic + string length.
+    const HEADER_LENGTH: usize = 1 + 8 + 8 + MAGIC.len() + 2;
+
+    if packet.len() < HEADER_LENGTH {
+        return Err(BedrockError::InvalidResponse {
+            reason: format!(
+                "expected at least {HEADER_LENGTH} bytes, got {}",
+                packet.len()
+            ),
+        });
+    }
+
+    if packet[0] != UNCONNECTED_PONG {
+        return Err(BedrockError::InvalidResponse {
+            reason: format!("expected the packet ID to be 0x1C, got {:#04X}", packet[0]),
+        });
+    }
+
+    if packet[17..33] != MAGIC {
+        return Err(BedrockError::InvalidResponse {
+            reason: "the packet is missing RakNet's magic bytes".to_owned(),
+        });
+    }
+
+    let length = usize::from(u16::from_be_bytes([packet[33], packet[34]]));
+    let data = packet
+        .get(HEADER_LENGTH..HEADER_LENGTH + length)
+        .ok_or_else(|| BedrockError::InvalidResponse {
+            reason: "the server ID string is truncated".to_owned(),
+        })?;
+
+    parse_server_id(&String::from_utf8_lossy(data))
+}
+
+/// Parse the semicolon-separated server ID string from the Unconnected Pong packet.
+///
+/// For example: `MCPE;Dedicated Server;800;1.21.80;0;10;1234;Bedrock level;Survival;1;19132;19133;`
+fn parse_server_id(server_id: &str) -> Result<Pong, BedrockError> {
+    let fields = server_id.split(';').collect::<Vec<_>>();
+    let invalid = |field: &str| BedrockError::InvalidResponse {
+        reason: format!("invalid {field} in server ID: {server_id:?}"),
+    };
+
+    let [edition, motd, protocol, version, online, max, rest @ ..] = fields.as_slice() else {
+        return Err(BedrockError::InvalidResponse {
+            reason: format!("expected at least 6 fields in server ID: {server_id:?}"),
+        });
+    };
+
+    // The remaining fields are the server GUID, sub-MOTD, and game mode (among others).
+    let optional = |i: usize| {
+        rest.get(i)
+            .filter(|field| !field.is_empty())
+            .map(|field| field.to_string())
+    };
+
+    Ok(Pong {
+        edition: edition.to_string(),
+        motd: motd.to_string(),
+        sub_motd: optional(1),
+        protocol: protocol.parse().map_err(|_| invalid("protocol"))?,
+        version: version.to_string(),
+        online: online.parse().map_err(|_| invalid("player count"))?,
+        max: max.parse().map_err(|_| invalid("max player count"))?,
+        game_mode: optional(2),
+    })
+}
+
+/// Represents errors that can occur while pinging a Bedrock Edition server.
+#[derive(Debug)]
+pub enum BedrockError {
+    /// The server's address could not be resolved.
+    ResolveFailed {
+        /// The underlying error that caused the failure.
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    /// There was a problem sending or receiving the packets.
+    IoFailed {
+        /// The underlying error that caused the failure.
+        source: std::io::Error,
+    },
+    /// The server did not respond in time.
+    TimedOut,
+    /// The server responded with something other than an Unconnected Pong packet.
+    InvalidResponse {
+        /// Describes what was wrong with the response.
+        reason: String,
+    },
+}
+
+impl std::fmt::Display for BedrockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ResolveFailed { source: _ } => write!(f, "failed to resolve server address"),
+            Self::IoFailed { source: _ } => write!(f, "failed to ping server"),
+            Self::TimedOut => write!(f, "no response from server. is it a Bedrock server?"),
+            Self::InvalidResponse { reason } => write!(f, "invalid response from server: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for BedrockError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ResolveFailed { source } => Some(source.as_ref()),
+            Self::IoFailed { source } => Some(source),
+            Self::TimedOut => None,
+            Self::InvalidResponse { reason: _ } => None,
+        }
+    }
+}
+
+impl BedrockError {
+    /// Creates an error indicating that the server's address could not be resolved.
+    pub fn resolve_failed(
+        source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    ) -> Self {
+        Self::ResolveFailed {
+            source: source.into(),
+        }
+    }
+
+    /// Creates an error indicating a failure to send or receive packets.
+    pub fn io_failed(source: std::io::Error) -> Self {
+        Self::IoFailed { source }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pong_packet(server_id: &str) -> Vec<u8> {
+        let mut packet = vec![UNCONNECTED_PONG];
+        packet.extend(1234i64.to_be_bytes());
+        packet.extend(5678i64.to_be_bytes());
+        packet.extend(MAGIC);
+        packet.extend((server_id.len() as u16).to_be_bytes());
+        packet.extend(server_id.as_bytes());
+        packet
+    }
+
+    #[test]
+    fn test_parse_pong_packet() {
+        let packet = pong_packet(
+            "MCPE;Dedicated Server;800;1.21.80;3;10;13253860892328930865;Bedrock level;Survival;1;19132;19133;",
+        );
+        let pong = parse_pong_packet(&packet).unwrap();
+
+        assert_eq!(pong.edition(), "MCPE");
+        assert_eq!(pong.motd(), "Dedicated Server");
+        assert_eq!(pong.sub_motd(), Some("Bedrock level"));
+        assert_eq!(pong.protocol(), 800);
+        assert_eq!(pong.version(), "1.21.80");
+        assert_eq!((pong.online(), pong.max()), (3, 10));
+        assert_eq!(pong.game_mode(), Some("Survival"));
+
+        // Some servers only send the required fields.
+        let pong = parse_pong_packet(&pong_packet("MCPE;Old Server;390;1.14.60;0;10")).unwrap();
+        assert_eq!(pong.sub_motd(), None);
+        assert_eq!(pong.game_mode(), None);
+    }
+
+    #[test]
+    fn test_parse_pong_packet_invalid() {
+        assert!(parse_pong_packet(&[UNCONNECTED_PONG, 0, 0]).is_err());
+        assert!(parse_pong_packet(&pong_packet("MCPE;Missing Fields")).is_err());
+        assert!(parse_pong_packet(&pong_packet("MCPE;Bad;x;1.21;0;10")).is_err());
+
+        let mut packet = pong_packet("MCPE;Server;800;1.21.80;0;10");
+        packet[21] = 0;
+        assert!(parse_pong_packet(&packet).is_err());
+
+        let mut packet = pong_packet("MCPE;Server;800;1.21.80;0;10");
+        packet.truncate(packet.len() - 1);
+        assert!(parse_pong_packet(&packet).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+//! This module defines the programs that can run a Minecraft server in the background.
+
+mod systemd;
+mod tmux;
+
+pub(crate) use systemd::SystemdBackend;
+pub(crate) use tmux::TmuxBackend;
+
+use crate::error::Error;
+
+pub(crate) trait Backend {
+    /// Run the server's `start.sh` script in the background.
+    fn start(&self, package: &axiom::Package) -> Result<(), Error>;
+
+    /// Ask the server to shut down.
+    ///
+    /// This function returns as soon as the request is sent; it does not wait for the server to
+    /// finish shutting down.
+    fn stop(&self, package: &axiom::Package) -> Result<(), Error>;
+
+    /// Check whether the server is currently running.
+    fn is_running(&self, package: &axiom::Package) -> Result<bool, Error>;
+
+    /// Connect the terminal to the server console, returning once the user detaches from it.
+    fn attach(&self, package: &axiom::Package) -> Result<(), Error>;
+
+    /// Run `command` in the server console.
+    fn send_command(&self, package: &axiom::Package, command: &str) -> Result<(), Error>;
+
+    /// Remove whatever the backend set up to run the server (e.g., its tmux window or systemd
+    /// unit), so nothing is left pointing at the package once it is deleted.
+    ///
+    /// The server should already be stopped. There is nothing to do if the server never started.
+    fn remove(&self, package: &axiom::Package) -> Result<(), Error>;
+}
+
+/// Describe how a command exited (e.g., `exit code 1`), for logging why a backend failed.
+///
+/// A process only has an exit code if it exited on its own. On Unix, a process that was killed
+/// by a signal has none, so the signal is described instead.
+pub(crate) fn describe_exit_status(status: std::process::ExitStatus) -> String {
+    if let Some(code) = status.code() {
+        return format!("exit code {code}");
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        if let Some(signal) = status.signal() {
+            return format!("signal {signal}");
+        }
+    }
+
+    status.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(unix)]
+    fn test_describe_exit_status() {
+        use std::os::unix::process::ExitStatusExt;
+
+        // The raw status is encoded the same way as `waitpid` does.
+        let exited = std::process::ExitStatus::from_raw(1 << 8);
+        assert_eq!(describe_exit_status(exited), "exit code 1");
+
+        let killed = std::process::ExitStatus::from_raw(libc::SIGKILL);
+        assert_eq!(describe_exit_status(killed), "signal 9");
+    }
+}
@@ -0,0 +1,127 @@
+use anyhow::Context;
+
+use crate::error::Error;
+
+/// Runs each server as a `systemd --user` service, for machines without tmux.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SystemdBackend;
+
+impl SystemdBackend {
+    pub(crate) fn new() -> Self {
+        Self
+    }
+
+    /// Get the name of the service unit for the package.
+    fn unit_name(package: &axiom::Package) -> String {
+        format!("axiom-{}.service", package.name())
+    }
+
+    /// Get the directory the user's systemd units are kept in.
+    fn unit_directory() -> Result<std::path::PathBuf, anyhow::Error> {
+        let config = dirs::config_dir().with_context(|| "failed to get config directory")?;
+        Ok(config.join("systemd/user"))
+    }
+
+    /// Write the service unit for the package into the user's systemd configuration directory.
+    ///
+    /// The unit is regenerated every time the server starts, so moving the package only requires
+    /// starting it again.
+    fn install_unit(package: &axiom::Package) -> Result<(), anyhow::Error> {
+        let units = Self::unit_directory()?;
+        std::fs::create_dir_all(&units)
+            .with_context(|| "failed to create systemd user unit directory")?;
+
+        let server = package.server();
+        let contents = format!(
+            "[Unit]\n\
+            Description=Minecraft server ({name}) managed by Axiom\n\
+            \n\
+            [Service]\n\
+            Type=simple\n\
+            WorkingDirectory={directory}\n\
+            ExecStart=\"{start_sh}\"\n",
+            name = package.name(),
+            directory = server.path().display(),
+            start_sh = server.start_sh().display(),
+        );
+
+        std::fs::write(units.join(Self::unit_name(package)), contents)
+            .with_context(|| "failed to write systemd unit")?;
+
+        systemctl(&["daemon-reload"])
+    }
+}
+
+impl super::Backend for SystemdBackend {
+    fn start(&self, package: &axiom::Package) -> Result<(), Error> {
+        Self::install_unit(package)?;
+        systemctl(&["start", &Self::unit_name(package)])?;
+        Ok(())
+    }
+
+    fn stop(&self, package: &axiom::Package) -> Result<(), Error> {
+        // systemd sends SIGTERM to every process in the service, which Paper treats as a request
+        // to shut down cleanly. `--no-block` lets the caller watch the logs while it shuts down.
+        systemctl(&["stop", "--no-block", &Self::unit_name(package)])?;
+        Ok(())
+    }
+
+    fn is_running(&self, package: &axiom::Package) -> Result<bool, Error> {
+        let status = std::process::Command::new("systemctl")
+            .args(["--user", "is-active", "--quiet", &Self::unit_name(package)])
+            .status()
+            .with_context(|| "failed to execute command 'systemctl'")?;
+
+        Ok(status.success())
+    }
+
+    fn attach(&self, package: &axiom::Package) -> Result<(), Error> {
+        // The service's output goes to the journal, and there is no console to type into.
+        let err = anyhow::anyhow!("the systemd backend does not support attaching to the console");
+        Err(Error::new(err).with_hint(|| {
+            format!(
+                "Run `journalctl --user -fu {}` to follow the server's output",
+                Self::unit_name(package)
+            )
+        }))
+    }
+
+    fn send_command(&self, _: &axiom::Package, _: &str) -> Result<(), Error> {
+        // There is no pane to type into; this needs a way to reach the console remotely (RCON).
+        let err = anyhow::anyhow!("the systemd backend does not support sending console commands");
+        Err(Error::new(err).with_hint(|| "Use the tmux backend to send commands to the console"))
+    }
+
+    fn remove(&self, package: &axiom::Package) -> Result<(), Error> {
+        let unit = Self::unit_directory()?.join(Self::unit_name(package));
+
+        match std::fs::remove_file(&unit) {
+            Ok(()) => systemctl(&["daemon-reload"])?,
+            // The server was never started with this backend.
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => {
+                return Err(anyhow::Error::new(err)
+                    .context(format!("failed to remove {}", unit.display()))
+                    .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Run `systemctl --user` with the given arguments.
+fn systemctl(args: &[&str]) -> Result<(), anyhow::Error> {
+    let status = std::process::Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .stdout(std::process::Stdio::null())
+        .status()
+        .with_context(|| "failed to execute command 'systemctl'")?;
+
+    if !status.success() {
+        anyhow::bail!("command 'systemctl --user {}' failed", args.join(" "));
+    }
+
+    Ok(())
+}
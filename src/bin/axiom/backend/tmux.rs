@@ -0,0 +1,174 @@
+use anyhow::Context;
+
+use crate::error::Error;
+
+/// Runs each server in its own window inside of a shared tmux session.
+#[derive(Debug, Clone)]
+pub(crate) struct TmuxBackend {
+    session: axiom::tmux::Session,
+}
+
+impl TmuxBackend {
+    pub(crate) fn new(session: axiom::tmux::Session) -> Self {
+        Self { session }
+    }
+
+    fn window(&self, package: &axiom::Package) -> axiom::tmux::Window {
+        axiom::tmux::Window::new(self.session.clone(), package.name().to_owned())
+    }
+}
+
+impl super::Backend for TmuxBackend {
+    fn start(&self, package: &axiom::Package) -> Result<(), Error> {
+        let server = package.server();
+
+        match axiom::tmux::Window::create(
+            &self.session,
+            package.name(),
+            server.path(),
+            "./start.sh",
+        ) {
+            Ok(_) => Ok(()),
+            Err(axiom::tmux::TmuxError::ExitFailure { status }) => {
+                tracing::error!(
+                    "command terminated with {}",
+                    super::describe_exit_status(status)
+                );
+
+                crate::bail!("failed to create tmux session");
+            }
+            Err(err) => Err(err).with_context(|| "failed to execute tmux command")?,
+        }
+    }
+
+    fn stop(&self, package: &axiom::Package) -> Result<(), Error> {
+        // Paper treats SIGTERM as a request to shut down cleanly, so send it to the JVM directly.
+        //
+        // Sending "stop" and "Enter" (or CTRL+C) into the pane assumes that there is no other
+        // command currently being typed into the console. If there is, the keys get mixed in with
+        // whatever the user was typing. We only fall back to CTRL+C if we can't find the JVM.
+        let window = self.window(package);
+        let java_pid = window.pane_pid().ok().and_then(find_java_process);
+
+        match java_pid {
+            Some(pid) if terminate(pid).is_ok() => {
+                tracing::debug!("sent SIGTERM to java process {pid}");
+            }
+            _ => {
+                tracing::debug!("failed to signal the java process directly; sending Ctrl+C");
+                window
+                    .send_keys(&["C-c"])
+                    .with_context(|| "failed to send Ctrl+C (SIGTERM) to tmux window")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_running(&self, package: &axiom::Package) -> Result<bool, Error> {
+        let running = self
+            .window(package)
+            .exists()
+            .with_context(|| "failed to check if the server is running")?;
+
+        Ok(running)
+    }
+
+    fn attach(&self, package: &axiom::Package) -> Result<(), Error> {
+        self.window(package)
+            .attach()
+            .with_context(|| "failed to attach to tmux window")?;
+
+        Ok(())
+    }
+
+    fn send_command(&self, package: &axiom::Package, command: &str) -> Result<(), Error> {
+        // Send the text literally (`-l`) so words like "Enter" in the command aren't treated as
+        // key names, then press Enter separately.
+        let window = self.window(package);
+        window
+            .send_keys(&["-l", command])
+            .and_then(|()| window.send_keys(&["Enter"]))
+            .with_context(|| "failed to send command to tmux window")?;
+
+        Ok(())
+    }
+
+    fn remove(&self, package: &axiom::Package) -> Result<(), Error> {
+        // The window should close on its own once `start.sh` exits, but make sure it's gone so we
+        // don't leave a dangling window pointing at a deleted directory.
+        let window = self.window(package);
+        let exists = window
+            .exists()
+            .with_context(|| "failed to check for the server's tmux window")?;
+
+        if exists {
+            window
+                .kill()
+                .with_context(|| "failed to close the server's tmux window")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Find the `java` process started by `start.sh`, searching the descendants of `parent`.
+#[cfg(unix)]
+fn find_java_process(parent: u32) -> Option<u32> {
+    let output = std::process::Command::new("ps")
+        .args(["-A", "-o", "pid=,ppid=,comm="])
+        .output()
+        .ok()?;
+
+    // Each line looks like: `[pid] [ppid] [command]`.
+    let processes = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pid = parts.next()?.parse::<u32>().ok()?;
+            let ppid = parts.next()?.parse::<u32>().ok()?;
+            let command = parts.next()?.to_owned();
+            Some((pid, ppid, command))
+        })
+        .collect::<Vec<_>>();
+
+    let mut queue = std::collections::VecDeque::from([parent]);
+
+    while let Some(current) = queue.pop_front() {
+        for (pid, ppid, command) in processes.iter() {
+            if *ppid != current {
+                continue;
+            }
+
+            if command.rsplit('/').next() == Some("java") {
+                return Some(*pid);
+            }
+
+            queue.push_back(*pid);
+        }
+    }
+
+    None
+}
+
+#[cfg(not(unix))]
+fn find_java_process(_: u32) -> Option<u32> {
+    None
+}
+
+/// Send SIGTERM to the process with the given ID.
+#[cfg(unix)]
+fn terminate(pid: u32) -> Result<(), std::io::Error> {
+    let pid = libc::pid_t::try_from(pid).map_err(std::io::Error::other)?;
+
+    // SAFETY: `kill` has no memory safety requirements; an invalid PID is reported via `errno`.
+    match unsafe { libc::kill(pid, libc::SIGTERM) } {
+        0 => Ok(()),
+        _ => Err(std::io::Error::last_os_error()),
+    }
+}
+
+#[cfg(not(unix))]
+fn terminate(_: u32) -> Result<(), std::io::Error> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
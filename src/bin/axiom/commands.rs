@@ -1,11 +1,28 @@
+mod attach;
 mod build;
+mod cache;
+mod clone;
+mod completions;
+mod delete;
+mod doctor;
+mod edit;
+mod eula;
+mod export;
+mod import;
+mod info;
 mod list;
 mod new;
+mod plugin;
+mod properties;
+mod prune;
+mod rename;
+mod say;
 mod start;
 mod status;
 mod status_ext;
 mod stop;
 mod update;
+mod version;
 
 use crate::context::Context;
 use crate::error::Error;
@@ -13,6 +30,106 @@ use crate::error::Error;
 pub(crate) const TMUX_SERVER_NAME: &str = "axiom";
 pub(crate) const TMUX_SESSION_NAME: &str = "servers";
 
+/// A hint for errors caused by `java` not being installed.
+pub(crate) const JAVA_NOT_FOUND_HINT: &str =
+    "install a JDK (e.g., `apt install openjdk-21-jre-headless`) and make sure `java` is in PATH";
+
+/// Ensure `tmux` is installed before running a command that depends on it.
+pub(crate) fn ensure_tmux_installed() -> Result<(), Error> {
+    axiom::tmux::ensure_installed().map_err(|err| {
+        Error::new(err)
+            .with_hint(|| "install tmux (e.g., `apt install tmux` or `brew install tmux`)")
+    })
+}
+
+/// Ensure the package's Java installation is new enough to run its version of Minecraft.
+///
+/// Running a server with an old JDK only fails once the server is already starting, with an
+/// `UnsupportedClassVersionError` buried in the logs, so check the version up front instead.
+pub(crate) fn ensure_java_compatible(package: &axiom::Package) -> Result<(), Error> {
+    let minecraft_version = package.manifest().server().version();
+    let Some(required) = axiom::java::required_version(minecraft_version) else {
+        return Ok(());
+    };
+
+    let java = package.server().java();
+    let installed = match axiom::java::installed_version(java) {
+        Ok(installed) => installed,
+        Err(err) if err.is_command_not_found() => {
+            return Err(Error::new(err).with_hint(|| JAVA_NOT_FOUND_HINT));
+        }
+        Err(err) => {
+            tracing::warn!("failed to check the installed version of Java: {err}");
+            return Ok(());
+        }
+    };
+
+    tracing::debug!("found Java {installed} at {}", java.display());
+
+    if installed < required {
+        let err = anyhow::anyhow!(
+            "Minecraft {minecraft_version} requires Java {required} or newer, \
+            but {} is Java {installed}",
+            java.display()
+        );
+        return Err(Error::new(err).with_hint(|| {
+            format!(
+                "install Java {required} (e.g., `apt install openjdk-{required}-jre-headless`) \
+                or set `java` in the [launcher] section of {} to point to it",
+                axiom::Manifest::FILENAME
+            )
+        }));
+    }
+
+    Ok(())
+}
+
+/// Check the manifest in `directory` for mistakes, printing every problem that was found.
+///
+/// A missing or unreadable manifest is not reported here; that is left to
+/// [`Context::package`], which gives a more specific error.
+pub(crate) fn validate_manifest_in(directory: &std::path::Path) -> Result<(), Error> {
+    let path = directory.join(axiom::Manifest::FILENAME);
+
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => validate_manifest(&path, &contents),
+        Err(_) => Ok(()),
+    }
+}
+
+/// Check `contents` for mistakes, printing every problem that was found.
+///
+/// `path` is only used to tell the user which file the problems are in.
+pub(crate) fn validate_manifest(path: &std::path::Path, contents: &str) -> Result<(), Error> {
+    use colored::Colorize;
+    use std::io::Write;
+
+    let Err(errors) = axiom::Manifest::validate(contents) else {
+        return Ok(());
+    };
+
+    let mut stderr = std::io::stderr().lock();
+
+    for error in errors.iter() {
+        writeln!(
+            stderr,
+            "{}: {}:{}:{}: {}",
+            "error".bold().red(),
+            path.display(),
+            error.line(),
+            error.column(),
+            error.message()
+        )
+        .ok();
+
+        if let Some(suggestion) = error.suggestion() {
+            writeln!(stderr, "  {}: {}", "help".bold().green(), suggestion).ok();
+        }
+    }
+
+    crate::bail!("found {} problem(s) in {}", errors.len(), path.display());
+}
+
 pub(crate) trait Run {
     /// Execute the subcommand.
     fn run(&self, ctx: &mut Context) -> Result<(), Error>;
@@ -20,15 +137,78 @@ pub(crate) trait Run {
 
 #[derive(clap::Subcommand)]
 pub(crate) enum Subcommand {
+    /// Connect to the console of the running server.
+    ///
+    /// Detach from the console (e.g., with Ctrl+B then D) to return to the shell, leaving the
+    /// server running.
+    Attach(attach::Attach),
+
     /// Apply any changes to the server.
     Build(build::Build),
 
+    /// Manage the server JARs that Axiom has downloaded.
+    Cache(cache::Cache),
+
+    /// Copy a package, giving the copy a fresh world unless `--with-world` is used.
+    Clone(clone::Clone),
+
+    /// Print a shell completion script.
+    ///
+    /// Save the output to wherever your shell loads completions from. For example:
+    ///
+    /// bash: axiom completions bash > ~/.local/share/bash-completion/completions/axiom
+    ///
+    /// zsh: axiom completions zsh > ~/.zfunc/_axiom
+    ///
+    /// fish: axiom completions fish > ~/.config/fish/completions/axiom.fish
+    #[command(hide = true)]
+    Completions(completions::Completions),
+
+    /// Remove the package, stopping the server if it is running.
+    Delete(delete::Delete),
+
+    /// Check that Axiom's dependencies and the current package are set up correctly.
+    Doctor(doctor::Doctor),
+
+    /// Open the manifest in a text editor, checking it for mistakes before saving.
+    Edit(edit::Edit),
+
+    /// Check whether the Minecraft EULA has been accepted, or accept it.
+    Eula(eula::Eula),
+
+    /// Pack the package into an archive that can be moved to another machine.
+    ///
+    /// The archive includes the server JAR itself, rather than a link to Axiom's cache. Use
+    /// `axiom import` to unpack it.
+    Export(export::Export),
+
+    /// Unpack a package created by `axiom export`.
+    Import(import::Import),
+
+    /// Summarize the current package and the state of its server.
+    Info(info::Info),
+
     /// Display which Minecraft servers are currently active.
     List(list::List),
 
     /// Create a new package.
     New(new::New),
 
+    /// Manage the plugins declared in the manifest.
+    Plugin(plugin::Plugin),
+
+    /// Compare the properties in the manifest to the server's `server.properties`.
+    Properties(properties::Properties),
+
+    /// Remove files that pile up inside of the package over time (e.g., old logs).
+    Prune(prune::Prune),
+
+    /// Change the name of the package.
+    Rename(rename::Rename),
+
+    /// Broadcast a message to everyone on the server (requires the tmux backend).
+    Say(say::Say),
+
     /// Run the server, allowing players to connect to the world.
     Start(start::Start),
 
@@ -43,24 +223,43 @@ pub(crate) enum Subcommand {
 
     /// Use a different Minecraft version.
     Update(update::Update),
+
+    /// Print the versions of Axiom and the current package's server.
+    Version(version::Version),
 }
 
 impl Subcommand {
-    pub(crate) fn run(&self) -> Result<(), Error> {
-        let mut ctx = Context::default();
-        self.handler().run(&mut ctx)
+    pub(crate) fn run(&self, ctx: &mut Context) -> Result<(), Error> {
+        self.handler().run(ctx)
     }
 
     pub(crate) fn handler(&self) -> &dyn Run {
         match self {
+            Self::Attach(handler) => handler,
             Self::Build(handler) => handler,
+            Self::Cache(handler) => handler,
+            Self::Clone(handler) => handler,
+            Self::Completions(handler) => handler,
+            Self::Delete(handler) => handler,
+            Self::Doctor(handler) => handler,
+            Self::Edit(handler) => handler,
+            Self::Eula(handler) => handler,
+            Self::Export(handler) => handler,
+            Self::Import(handler) => handler,
+            Self::Info(handler) => handler,
             Self::List(handler) => handler,
             Self::New(handler) => handler,
+            Self::Plugin(handler) => handler,
+            Self::Properties(handler) => handler,
+            Self::Prune(handler) => handler,
+            Self::Rename(handler) => handler,
+            Self::Say(handler) => handler,
             Self::Start(handler) => handler,
             Self::Status(handler) => handler,
             Self::StatusExt(handler) => handler,
             Self::Stop(handler) => handler,
             Self::Update(handler) => handler,
+            Self::Version(handler) => handler,
         }
     }
 }
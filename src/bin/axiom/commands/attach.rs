@@ -0,0 +1,26 @@
+//! This module implements the `attach` command, which connects the terminal to the console of the
+//! package's running server.
+
+use anyhow::Context;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Attach;
+
+impl crate::commands::Run for Attach {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let package = ctx
+            .package()
+            .with_context(|| "failed to get package manifest")?;
+        let backend = ctx.backend()?;
+
+        if !backend.is_running(&package)? {
+            return Err(crate::error::Error::new_with_hint(
+                "start the server with `axiom start` first",
+                anyhow::anyhow!("the server is not running"),
+            ));
+        }
+
+        // This blocks until the user detaches (e.g., with Ctrl+B then D).
+        backend.attach(&package)
+    }
+}
@@ -1,23 +1,72 @@
+#[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
 use anyhow::Context;
 use colored::Colorize;
-use std::io::Write;
+use std::io::{BufRead, IsTerminal, Write};
 
 #[derive(clap::Args)]
 pub struct Build {
     /// Accept the Minecraft EULA (End User License Agreement) without prompting for user input.
     #[arg(long, short = 'y')]
     pub(crate) accept_eula: bool,
+
+    /// Report which files are out of date with the manifest, without changing anything.
+    ///
+    /// Exits with an error if any file would be changed by running `build`.
+    #[arg(long, conflicts_with = "accept_eula")]
+    pub(crate) check: bool,
+
+    /// Replace `server.properties` with only the properties in the manifest.
+    ///
+    /// By default, only the keys set in the manifest are changed, and everything else in
+    /// `server.properties` is kept.
+    #[arg(long)]
+    pub(crate) overwrite: bool,
+
+    /// The maximum number of seconds to wait for the server to generate its files, the first
+    /// time the package is built.
+    #[arg(long, value_name = "SECONDS", default_value_t = DEFAULT_INIT_TIMEOUT)]
+    pub(crate) init_timeout: u64,
 }
 
+/// The default value of `--init-timeout`.
+pub(crate) const DEFAULT_INIT_TIMEOUT: u64 = 300;
+
 impl crate::commands::Run for Build {
     fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        super::validate_manifest_in(&ctx.package_directory()?)?;
+
         let package = ctx
             .package()
             .with_context(|| "failed to get package manifest")?;
 
+        if self.check {
+            return check(ctx, &package, self.overwrite);
+        }
+
         let server = package.server();
+
+        let custom_java = package
+            .manifest()
+            .launcher()
+            .and_then(|launcher| launcher.java())
+            .is_some();
+
+        if custom_java && !server.java().is_file() {
+            tracing::warn!(
+                "the java executable set in the manifest does not exist: {}",
+                server.java().display()
+            );
+        }
+
+        super::ensure_java_compatible(&package)?;
+
+        if let Some(script) = package.manifest().server().pre_build() {
+            tracing::info!("running the pre-build script");
+            run_hook(&package, "pre-build", script)?;
+        }
+
         std::fs::create_dir_all(server.path())
             .with_context(|| "failed to create 'server' directory")?;
 
@@ -49,9 +98,12 @@ impl crate::commands::Run for Build {
                 &super::update::Update {
                     version: Some(version.to_owned()),
                     build: Some(build),
+                    all: false,
                     allow_experimental: true,
                     allow_downgrade: true,
                     timeout: 120,
+                    dry_run: false,
+                    jobs: std::num::NonZeroUsize::MIN,
                 },
                 ctx,
             )?
@@ -64,26 +116,49 @@ impl crate::commands::Run for Build {
             let server_jar = server.server_jar();
             assert!(server_jar.exists());
 
-            _ = std::process::Command::new("java")
-                .args([
-                    "-jar",
-                    server_jar
-                        .to_str()
-                        .expect("expected path to be valid unicode"),
-                    "--initSettings",
-                ])
+            let child = std::process::Command::new(server.java())
+                .arg("-jar")
+                .arg(server_jar)
+                .arg("--initSettings")
                 .current_dir(server.path())
+                .stdin(std::process::Stdio::null())
                 .stdout(std::process::Stdio::null())
                 .stderr(std::process::Stdio::null())
-                .status()
-                .with_context(|| "failed to execute command 'java'")?;
+                .spawn();
+
+            let timeout = std::time::Duration::from_secs(self.init_timeout);
+            let status = child.and_then(|mut child| wait_with_timeout(&mut child, timeout));
+
+            if let Ok(None) = status {
+                return Err(crate::error::Error::new_with_hint(
+                    format!(
+                        "check that another instance of the server isn't running, and remove \
+                        `session.lock` from the world directory if it was left behind; use \
+                        {} to wait longer",
+                        "--init-timeout".yellow()
+                    ),
+                    anyhow::anyhow!(
+                        "the server took longer than {} seconds to generate its files",
+                        self.init_timeout
+                    ),
+                ));
+            }
+
+            if let Err(err) = status {
+                let not_found = err.kind() == std::io::ErrorKind::NotFound;
+                let err = crate::error::Error::new(
+                    anyhow::Error::new(err).context("failed to execute command 'java'"),
+                );
+
+                return Err(match not_found {
+                    true => err.with_hint(|| super::JAVA_NOT_FOUND_HINT),
+                    false => err,
+                });
+            }
         }
 
-        if let Some(properties) = package.manifest().properties() {
-            // Overwrite `server.properties` with the properties in the config file. Any missing
-            // keys should be generated automatically by the server on the next run.
+        if let Some(contents) = server_properties_contents(&package, self.overwrite)? {
             let path = server.server_properties();
-            let contents = properties.to_server_properties();
             std::fs::write(path, contents).with_context(|| "failed to update server.properties")?;
         }
 
@@ -94,89 +169,426 @@ impl crate::commands::Run for Build {
                 Err(err) => tracing::warn!("failed to read the `eula.txt` file: {err}"),
             }
 
-            if !self.accept_eula && !prompt_user_to_accept_eula() {
-                // User was prompted to accept the EULA interactively but they declined.
-                return Ok(());
+            if !self.accept_eula {
+                // Prompting would wait forever if nobody is around to answer.
+                if !std::io::stdin().is_terminal() {
+                    return Err(crate::error::Error::new_with_hint(
+                        "run `axiom eula --accept` or pass `--accept-eula` to accept it",
+                        anyhow::anyhow!("the Minecraft EULA has not been accepted"),
+                    ));
+                }
+
+                if !prompt_user_to_accept_eula(ctx) {
+                    // User was prompted to accept the EULA interactively but they declined.
+                    return Ok(());
+                }
             }
 
-            std::fs::write(server.eula_txt(), "eula=true")
+            server
+                .accept_eula()
                 .with_context(|| "failed to write to eula.txt")?;
         }
 
         tracing::info!("generating the start script");
-        let memory = package
-            .manifest()
-            .launcher()
-            .and_then(|launcher| launcher.memory())
-            .unwrap_or("4096M");
+        let start_script = start_script(server);
+        std::fs::write(start_script, start_script_contents(&package))
+            .with_context(|| format!("failed to write to {}", start_script.display()))?;
 
-        let mut preset = package
-            .manifest()
-            .launcher()
-            .map(|launcher| launcher.preset())
-            .unwrap_or(&axiom::manifest::Preset::None)
-            .flags()
-            .join(" ");
+        // Batch files don't need to be marked as executable on Windows.
+        #[cfg(unix)]
+        {
+            tracing::info!("making the start script executable");
+            let metadata = start_script
+                .metadata()
+                .with_context(|| "failed to get start.sh metadata")?;
 
-        if !preset.is_empty() {
-            preset += " ";
+            let permissions = metadata.permissions();
+            // Give the user permission to execute the file, while leaving all other permissions
+            // untouched. This is effectively the same as running `chmod u+x` on the file.
+            let mode = permissions.mode() | 0o700;
+            std::fs::set_permissions(start_script, std::fs::Permissions::from_mode(mode))
+                .with_context(|| "failed to make the start script executable")?;
         }
 
-        let jvm_args = package
-            .manifest()
-            .launcher()
-            .and_then(|launcher| launcher.jvm_args())
-            .unwrap_or_default()
-            .join(" ");
+        if let Some(script) = package.manifest().server().post_build() {
+            tracing::info!("running the post-build script");
+            run_hook(&package, "post-build", script)?;
+        }
 
-        let game_args = package
-            .manifest()
-            .launcher()
-            .and_then(|launcher| launcher.game_args())
-            .unwrap_or_default()
-            .join(" ");
-
-        assert!(preset.is_empty() || preset.ends_with(" "));
-        let contents = format!(
-            "#!/usr/bin/bash\n\
-            \n\
-            java -Xms{memory} -Xmx{memory} {preset}{jvm_args} -jar ./server.jar {game_args}"
-        );
+        ctx.emit(crate::event::Event::Built);
 
-        std::fs::write(server.start_sh(), contents)
-            .with_context(|| "failed to write to start.sh")?;
+        if !ctx.porcelain() {
+            let mut stderr = std::io::stderr().lock();
+            writeln!(stderr, "✅ the Minecraft server is ready!").ok();
+        }
 
-        tracing::info!("making the start script executable");
-        let metadata = package
-            .server()
-            .start_sh()
-            .metadata()
-            .with_context(|| "failed to get start.sh metadata")?;
+        Ok(())
+    }
+}
 
-        let permissions = metadata.permissions();
-        // Give the user permission to execute the file, while leaving all other permissions
-        // untouched. This is effectively the same as running `chmod u+x` on the file.
-        let mode = permissions.mode() | 0o700;
-        std::fs::set_permissions(server.start_sh(), std::fs::Permissions::from_mode(mode))
-            .with_context(|| "failed to make the start script executable")?;
+/// Compare the server's files to what `build` would generate, printing the differences.
+fn check(
+    ctx: &crate::context::Context,
+    package: &axiom::Package,
+    overwrite: bool,
+) -> Result<(), crate::error::Error> {
+    let server = package.server();
+    let manifest = package.manifest().server();
+    let mut stdout = ctx.output();
+    let mut outdated = 0;
 
-        let mut stderr = std::io::stderr().lock();
-        writeln!(stderr, "✅ the Minecraft server is ready!").ok();
+    match server.installed_build_info() {
+        Ok(info) if (info.version(), info.build()) == (manifest.version(), manifest.build()) => {}
+        Ok(info) => {
+            writeln!(
+                stdout,
+                "{}: installed {} (#{}), but the manifest uses {} (#{})",
+                "server.jar".bold(),
+                info.version(),
+                info.build(),
+                manifest.version(),
+                manifest.build()
+            )
+            .ok();
+            outdated += 1;
+        }
+        Err(axiom::package::ServerBuildInfoError::ServerJarNotFound { .. }) => {
+            writeln!(stdout, "{}: not installed", "server.jar".bold()).ok();
+            outdated += 1;
+        }
+        Err(err) => tracing::warn!("failed to get build information for current server JAR: {err}"),
+    }
 
-        Ok(())
+    let mut files = vec![(start_script(server), start_script_contents(package))];
+
+    if let Some(contents) = server_properties_contents(package, overwrite)? {
+        files.push((server.server_properties(), contents));
+    }
+
+    for (path, expected) in files {
+        let current = std::fs::read_to_string(path).unwrap_or_default();
+
+        if current == expected {
+            continue;
+        }
+
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        writeln!(stdout, "{}", format!("--- {name} (current)").bold()).ok();
+        writeln!(stdout, "{}", format!("+++ {name} (expected)").bold()).ok();
+
+        for (change, line) in diff_lines(&current, &expected) {
+            match change {
+                Change::Removed => writeln!(stdout, "{}", format!("-{line}").red()).ok(),
+                Change::Added => writeln!(stdout, "{}", format!("+{line}").green()).ok(),
+            };
+        }
+
+        outdated += 1;
+    }
+
+    if !server.has_accepted_eula().unwrap_or(false) {
+        writeln!(
+            stdout,
+            "{}: the Minecraft EULA has not been accepted",
+            "eula.txt".bold()
+        )
+        .ok();
+        outdated += 1;
+    }
+
+    if outdated > 0 {
+        return Err(crate::error::Error::new_with_hint(
+            "run `axiom build` to update them",
+            anyhow::anyhow!("{outdated} file(s) are out of date with the manifest"),
+        ));
+    }
+
+    if !ctx.porcelain() {
+        writeln!(
+            std::io::stderr(),
+            "🟢 the server is up to date with the manifest"
+        )
+        .ok();
+    }
+    Ok(())
+}
+
+/// Describes how a line differs between two versions of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Removed,
+    Added,
+}
+
+/// Find the lines that were removed from `old` and added in `new`, in order.
+///
+/// This uses the longest common subsequence of lines, which is plenty fast for the small files
+/// `build` generates.
+fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<(Change, &'a str)> {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+
+    // `lengths[i][j]` is the length of the longest common subsequence of `old[i..]` and
+    // `new[j..]`.
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = match old[i] == new[j] {
+                true => lengths[i + 1][j + 1] + 1,
+                false => lengths[i + 1][j].max(lengths[i][j + 1]),
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lengths[i + 1][j] >= lengths[i][j + 1]) {
+            changes.push((Change::Removed, old[i]));
+            i += 1;
+        } else {
+            changes.push((Change::Added, new[j]));
+            j += 1;
+        }
+    }
+
+    changes
+}
+
+/// Get the contents `build` writes to `server.properties`, if the manifest has any properties.
+///
+/// The properties are merged into the existing `server.properties`, unless `overwrite` is set.
+fn server_properties_contents(
+    package: &axiom::Package,
+    overwrite: bool,
+) -> Result<Option<String>, crate::error::Error> {
+    let Some(properties) = package.manifest().properties() else {
+        return Ok(None);
+    };
+
+    let existing = match overwrite {
+        true => None,
+        false => std::fs::read_to_string(package.server().server_properties()).ok(),
+    };
+
+    // When overwriting, any missing keys are generated by the server on the next run.
+    let contents = match existing {
+        Some(existing) => properties.merge_into_server_properties(&existing),
+        None => properties.to_server_properties(),
+    }
+    .with_context(|| "failed to generate server.properties")?;
+
+    Ok(Some(contents))
+}
+
+/// Get the path `build` writes the start script to: `start.bat` on Windows, and `start.sh`
+/// everywhere else.
+pub(crate) fn start_script(server: &axiom::package::Server) -> &std::path::Path {
+    if cfg!(windows) {
+        server.start_bat()
+    } else {
+        server.start_sh()
+    }
+}
+
+/// Get the contents `build` writes to the start script (see [`start_script`]).
+fn start_script_contents(package: &axiom::Package) -> String {
+    let command = start_command(package.manifest().launcher(), package.server().java());
+
+    if cfg!(windows) {
+        // `cmd` expects CRLF line endings, and echoes every command unless told not to.
+        format!("@echo off\r\n\r\n{command}\r\n")
+    } else {
+        format!("#!/usr/bin/bash\n\n{command}")
+    }
+}
+
+/// The number of lines of output from a failed hook script to include in the error.
+const HOOK_OUTPUT_TAIL: usize = 10;
+
+/// Run one of the scripts from the manifest's `server` section (e.g., `pre_build`).
+///
+/// The script runs from the package directory. Its output is passed through to stderr line by
+/// line, with each line prefixed by `[name]` so it stands out from Axiom's own output. Stdout is
+/// left for Axiom's results (and the events printed by `--porcelain`). A non-zero exit status
+/// aborts the build.
+fn run_hook(
+    package: &axiom::Package,
+    name: &str,
+    script: &std::path::Path,
+) -> Result<(), crate::error::Error> {
+    let mut child = hook_command(package, script)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to execute script '{}'", script.display()))?;
+
+    let stdout = child.stdout.take().expect("expected stdout to be piped");
+    let stderr = child.stderr.take().expect("expected stderr to be piped");
+
+    let prefix = format!("[{name}]").dimmed();
+    let tail = std::sync::Mutex::new(std::collections::VecDeque::new());
+
+    // Read both streams at the same time, so the script can't block on a full pipe.
+    std::thread::scope(|scope| {
+        scope.spawn(|| forward_lines(stdout, std::io::stderr(), &prefix, &tail));
+        scope.spawn(|| forward_lines(stderr, std::io::stderr(), &prefix, &tail));
+    });
+
+    let status = child
+        .wait()
+        .with_context(|| format!("failed to wait for script '{}'", script.display()))?;
+
+    if !status.success() {
+        let tail = tail.into_inner().unwrap();
+        let mut message = format!("{name} script '{}' exited with {status}", script.display());
+
+        if !tail.is_empty() {
+            message.push_str("; the last lines of output were:");
+
+            for line in tail {
+                message.push_str(&format!("\n    {line}"));
+            }
+        }
+
+        crate::bail!(anyhow::anyhow!(message));
+    }
+
+    Ok(())
+}
+
+/// Copy each line from `reader` to `writer` with `prefix` in front of it, remembering the last few
+/// lines in `tail`.
+fn forward_lines(
+    reader: impl std::io::Read,
+    mut writer: impl std::io::Write,
+    prefix: &impl std::fmt::Display,
+    tail: &std::sync::Mutex<std::collections::VecDeque<String>>,
+) {
+    for line in std::io::BufReader::new(reader).lines() {
+        let Ok(line) = line else { break };
+        writeln!(writer, "{prefix} {line}").ok();
+
+        let mut tail = tail.lock().unwrap();
+        if tail.len() == HOOK_OUTPUT_TAIL {
+            tail.pop_front();
+        }
+        tail.push_back(line);
+    }
+}
+
+/// Prepare the command to run a hook script, passing information about the build through
+/// environment variables so the script doesn't need to parse the manifest itself.
+fn hook_command(package: &axiom::Package, script: &std::path::Path) -> std::process::Command {
+    let server = package.manifest().server();
+    let mut command = std::process::Command::new(package.path().join(script));
+    command
+        .current_dir(package.path())
+        .env("AXIOM_PACKAGE_NAME", package.name())
+        .env("AXIOM_SERVER_VERSION", server.version())
+        .env("AXIOM_SERVER_BUILD", server.build().to_string())
+        .env("AXIOM_SERVER_DIR", package.server().path());
+    command
+}
+
+/// Build the command used to run the server JAR, based on the manifest's `launcher` section.
+///
+/// The command looks something like this:
+///
+/// ```txt
+/// [java] -Xms[memory] -Xmx[memory] [preset] [jvm_args] -jar ./server.jar [game_args]
+/// ```
+///
+/// If `memory` is not set, the server gets 4096M.
+pub(crate) fn start_command(
+    launcher: Option<&axiom::manifest::Launcher>,
+    java: &std::path::Path,
+) -> String {
+    let memory = launcher
+        .and_then(|launcher| launcher.memory())
+        .unwrap_or("4096M");
+
+    let mut preset = launcher
+        .map(|launcher| launcher.preset())
+        .unwrap_or(&axiom::manifest::Preset::None)
+        .flags()
+        .join(" ");
+
+    if !preset.is_empty() {
+        preset += " ";
+    }
+
+    let jvm_args = launcher
+        .and_then(|launcher| launcher.jvm_args())
+        .unwrap_or_default()
+        .join(" ");
+
+    let game_args = launcher
+        .and_then(|launcher| launcher.game_args())
+        .unwrap_or_default()
+        .join(" ");
+
+    assert!(preset.is_empty() || preset.ends_with(" "));
+    // Quote the path in case it contains spaces, since it is written into a shell script. `cmd`
+    // only understands double quotes.
+    let java = match java.to_string_lossy() {
+        java if java.contains(char::is_whitespace) && cfg!(windows) => format!("\"{java}\""),
+        java if java.contains(char::is_whitespace) => format!("'{java}'"),
+        java => java.into_owned(),
+    };
+
+    format!("{java} -Xms{memory} -Xmx{memory} {preset}{jvm_args} -jar ./server.jar {game_args}")
+}
+
+/// Wait for `child` to exit, killing it if it takes longer than `timeout`.
+///
+/// Returns `None` if the child was killed.
+fn wait_with_timeout(
+    child: &mut std::process::Child,
+    timeout: std::time::Duration,
+) -> std::io::Result<Option<std::process::ExitStatus>> {
+    let deadline = std::time::Instant::now() + timeout;
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+
+        if std::time::Instant::now() >= deadline {
+            tracing::debug!("killing process {} after {timeout:?}", child.id());
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(100));
     }
 }
 
 /// Prompts the user to interactively accept the Minecraft EULA.
-fn prompt_user_to_accept_eula() -> bool {
-    println!(
+fn prompt_user_to_accept_eula(ctx: &crate::context::Context) -> bool {
+    let mut stdout = ctx.output();
+    writeln!(
+        stdout,
         "{}: {}",
         "You must accept the Minecraft EULA before continuing".bold(),
         "https://aka.ms/MinecraftEULA".underline().cyan()
-    );
-    print!("{} {} (y/N): ", "*".cyan(), "Accept and continue?".bold());
+    )
+    .ok();
+    write!(
+        stdout,
+        "{} {} (y/N): ",
+        "*".cyan(),
+        "Accept and continue?".bold()
+    )
+    .ok();
     #[rustfmt::skip]
-    std::io::stdout().flush().expect("failed to print full prompt");
+    stdout.flush().expect("failed to print full prompt");
 
     let mut input = String::new();
     std::io::stdin()
@@ -185,3 +597,122 @@ fn prompt_user_to_accept_eula() -> bool {
 
     input.trim().to_lowercase() == "y"
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axiom::manifest::{Backend, Launcher, Preset};
+
+    #[test]
+    #[cfg(unix)]
+    fn test_wait_with_timeout() {
+        // A stand-in for a server that hangs while generating its files.
+        let mut child = std::process::Command::new("sleep")
+            .arg("60")
+            .spawn()
+            .unwrap();
+
+        let start = std::time::Instant::now();
+        let status = wait_with_timeout(&mut child, std::time::Duration::from_millis(200)).unwrap();
+        assert!(status.is_none());
+        assert!(start.elapsed() < std::time::Duration::from_secs(10));
+        // The child was killed and reaped, rather than left running.
+        assert!(child.try_wait().unwrap().is_some());
+
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let status = wait_with_timeout(&mut child, std::time::Duration::from_secs(10)).unwrap();
+        assert!(status.is_some_and(|status| status.success()));
+    }
+
+    #[test]
+    fn test_start_command_memory() {
+        let jvm_args = vec!["-XX:+UseG1GC".to_owned()];
+        let launcher = Launcher::new(
+            Preset::None,
+            Some("8G".to_owned()),
+            Some(jvm_args),
+            None,
+            Backend::Tmux,
+            None,
+        );
+        let command = start_command(Some(&launcher), std::path::Path::new("java"));
+        assert!(
+            command.starts_with("java -Xms8G -Xmx8G -XX:+UseG1GC -jar"),
+            "{command}"
+        );
+
+        let launcher = Launcher::new(Preset::None, None, None, None, Backend::Tmux, None);
+        let command = start_command(Some(&launcher), std::path::Path::new("java"));
+        assert!(
+            command.starts_with("java -Xms4096M -Xmx4096M "),
+            "{command}"
+        );
+
+        let command = start_command(None, std::path::Path::new("java"));
+        assert!(
+            command.starts_with("java -Xms4096M -Xmx4096M "),
+            "{command}"
+        );
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "a=1\nb=2\nc=3\n";
+        let new = "a=1\nb=20\nc=3\nd=4\n";
+
+        let expected = vec![
+            (Change::Removed, "b=2"),
+            (Change::Added, "b=20"),
+            (Change::Added, "d=4"),
+        ];
+        assert_eq!(diff_lines(old, new), expected);
+        assert!(diff_lines(new, new).is_empty());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_start_script_contents() {
+        let package = crate::testing::package(std::path::Path::new("example"), "");
+
+        assert_eq!(start_script(package.server()), package.server().start_sh());
+        assert_eq!(
+            start_script_contents(&package),
+            "#!/usr/bin/bash\n\njava -Xms4096M -Xmx4096M  -jar ./server.jar "
+        );
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn test_start_script_contents() {
+        let package = crate::testing::package(std::path::Path::new("example"), "");
+
+        assert_eq!(start_script(package.server()), package.server().start_bat());
+        assert_eq!(
+            start_script_contents(&package),
+            "@echo off\r\n\r\njava -Xms4096M -Xmx4096M  -jar ./server.jar \r\n"
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_hook_command_env() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path();
+
+        let script = path.join("hook.sh");
+        let contents = "#!/bin/sh\n\
+            echo \"$AXIOM_PACKAGE_NAME $AXIOM_SERVER_VERSION $AXIOM_SERVER_BUILD $AXIOM_SERVER_DIR\"\n";
+        std::fs::write(&script, contents).unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o700)).unwrap();
+
+        let package = crate::testing::package(path, "");
+
+        let output = hook_command(&package, std::path::Path::new("./hook.sh"))
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        let expected = format!("example 1.21.6 34 {}\n", path.join("server").display());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), expected);
+    }
+}
@@ -0,0 +1,27 @@
+//! This module implements the `cache` command, which manages the server JARs Axiom has downloaded.
+
+mod clean;
+
+pub(crate) use clean::{format_size, jars_in_use};
+
+use crate::commands::Run;
+
+#[derive(clap::Args)]
+pub struct Cache {
+    #[command(subcommand)]
+    command: CacheCommand,
+}
+
+#[derive(clap::Subcommand)]
+enum CacheCommand {
+    /// Remove the cached server JARs that no package is using anymore.
+    Clean(clean::Clean),
+}
+
+impl Run for Cache {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        match &self.command {
+            CacheCommand::Clean(handler) => handler.run(ctx),
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! This module implements the `cache clean` command, which removes cached server JARs that are no
+//! longer used by any package.
+
+use std::collections::HashSet;
+use std::io::Write;
+
+use anyhow::Context;
+use colored::Colorize;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Clean {
+    /// The directory containing the packages. Defaults to the current directory.
+    ///
+    /// The directory itself and each of its immediate subdirectories are checked for packages,
+    /// the same as `list`. Packages created with `new` are always checked, wherever they are.
+    #[arg(long)]
+    path: Option<std::path::PathBuf>,
+
+    /// List the JARs that would be removed, without removing them.
+    #[arg(long, short = 'n')]
+    dry_run: bool,
+
+    /// Keep the N newest builds of each Minecraft version, even if no package is using them.
+    #[arg(long, value_name = "N", default_value = "0")]
+    keep_latest: usize,
+}
+
+impl crate::commands::Run for Clean {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let directory = match &self.path {
+            Some(path) => path.to_owned(),
+            None => ctx.directory()?,
+        };
+
+        let registered =
+            crate::registry::all().with_context(|| "failed to read the package registry")?;
+        let mut packages = Vec::new();
+
+        for path in crate::commands::list::find_packages(&directory)
+            .into_iter()
+            .chain(registered)
+        {
+            let path = std::path::absolute(&path).unwrap_or(path);
+            if !packages.contains(&path) {
+                packages.push(path);
+            }
+        }
+
+        if packages.is_empty() {
+            return Err(crate::error::Error::new_with_hint(
+                "use `--path` to choose the directory that contains your packages",
+                anyhow::anyhow!("found no packages in {}", directory.display()),
+            ));
+        }
+
+        let in_use = jars_in_use(&packages);
+
+        let jars = ctx.jars().with_context(|| "failed to get server JARs")?;
+        let builds = axiom::paper::cached_builds(&jars);
+        let unused = unused_builds(&builds, &in_use, self.keep_latest);
+
+        let mut stderr = std::io::stderr().lock();
+        let mut reclaimed = 0;
+
+        for build in &unused {
+            let path = jars.join(build.download_name());
+            let size = path.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+
+            if self.dry_run {
+                writeln!(
+                    stderr,
+                    "would remove {} ({})",
+                    path.display(),
+                    format_size(size)
+                )
+                .ok();
+            } else {
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("failed to remove {}", path.display()))?;
+                if !ctx.quiet() {
+                    writeln!(
+                        stderr,
+                        "🗑️ removed {} ({})",
+                        path.display(),
+                        format_size(size)
+                    )
+                    .ok();
+                }
+            }
+
+            reclaimed += size;
+        }
+
+        let summary = match (self.dry_run, unused.len()) {
+            (_, 0) => "no unused server JARs found".to_owned(),
+            (true, count) => format!(
+                "{count} server JAR(s) would be removed, reclaiming {}",
+                format_size(reclaimed)
+            ),
+            (false, count) => format!(
+                "removed {count} server JAR(s), reclaiming {}",
+                format_size(reclaimed)
+            ),
+        };
+        writeln!(stderr, "{}", summary.bold()).ok();
+
+        Ok(())
+    }
+}
+
+/// Get the file names of the cached server JARs used by the packages at `packages`.
+pub(crate) fn jars_in_use(packages: &[std::path::PathBuf]) -> HashSet<String> {
+    let mut in_use = HashSet::new();
+
+    for package_path in packages {
+        // A package may still be linked to an older JAR if it hasn't been rebuilt since its
+        // manifest was changed, so keep both.
+        let linked = std::fs::read_link(package_path.join("server/server.jar")).ok();
+        if let Some(name) = linked.as_deref().and_then(|target| target.file_name()) {
+            in_use.insert(name.to_string_lossy().into_owned());
+        }
+
+        match axiom::Manifest::from_directory(package_path) {
+            Ok(manifest) => {
+                let server = manifest.server();
+                in_use.insert(format!("paper-{}-{}.jar", server.version(), server.build()));
+            }
+            Err(err) => tracing::warn!("skipping {}: {err}", package_path.display()),
+        }
+    }
+
+    in_use
+}
+
+/// Get the builds that are not `in_use`, except for the newest `keep_latest` builds of each
+/// Minecraft version.
+///
+/// `builds` must be sorted oldest to newest (see [`axiom::paper::cached_builds`]).
+fn unused_builds<'a>(
+    builds: &'a [axiom::paper::Build],
+    in_use: &HashSet<String>,
+    keep_latest: usize,
+) -> Vec<&'a axiom::paper::Build> {
+    builds
+        .iter()
+        .enumerate()
+        .filter(|(i, build)| {
+            let newer = builds[i + 1..]
+                .iter()
+                .filter(|other| other.version() == build.version())
+                .count();
+            newer >= keep_latest
+        })
+        .map(|(_, build)| build)
+        .filter(|build| !in_use.contains(build.download_name()))
+        .collect()
+}
+
+/// Format `bytes` using the largest unit that keeps the number above 1 (e.g., `1.5 MiB`).
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut size = bytes as f64;
+    let mut unit = "B";
+
+    for next in UNITS {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next;
+    }
+
+    format!("{size:.1} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unused_builds() {
+        let builds = [
+            ("1.21.4", 10),
+            ("1.21.4", 15),
+            ("1.21.6", 30),
+            ("1.21.6", 34),
+        ]
+        .map(|(version, number)| {
+            axiom::paper::Build::new(
+                version.to_owned(),
+                number,
+                axiom::paper::Channel::Default,
+                format!("paper-{version}-{number}.jar"),
+            )
+        });
+        let in_use = HashSet::from(["paper-1.21.4-10.jar".to_owned()]);
+
+        let names = |keep_latest| {
+            unused_builds(&builds, &in_use, keep_latest)
+                .iter()
+                .map(|build| build.download_name().to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(0),
+            [
+                "paper-1.21.4-15.jar",
+                "paper-1.21.6-30.jar",
+                "paper-1.21.6-34.jar"
+            ]
+        );
+        assert_eq!(names(1), ["paper-1.21.6-30.jar"]);
+        assert!(names(2).is_empty());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(50 * 1024 * 1024), "50.0 MiB");
+    }
+}
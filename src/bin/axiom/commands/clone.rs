@@ -0,0 +1,258 @@
+//! This module implements the `clone` command, which creates a copy of an existing package (e.g.,
+//! a staging copy of a server).
+
+use std::io::Write;
+
+use anyhow::Context;
+use colored::Colorize;
+
+#[derive(clap::Args)]
+pub struct Clone {
+    /// Path to the package to copy.
+    source: std::path::PathBuf,
+
+    /// Path for where to set up the new package.
+    destination: std::path::PathBuf,
+
+    /// A name for the new package. Defaults to the directory name.
+    #[clap(long)]
+    name: Option<String>,
+
+    /// Also copy the worlds. By default, the new server generates a fresh world.
+    #[clap(long)]
+    with_world: bool,
+}
+
+impl crate::commands::Run for Clone {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let manifest = axiom::Manifest::from_directory(&self.source).map_err(|err| {
+            crate::error::Error::new(err).with_hint(|| {
+                format!(
+                    "make sure {} is a package containing {}",
+                    self.source.display(),
+                    axiom::Manifest::FILENAME
+                )
+            })
+        })?;
+        let source = axiom::Package::new(self.source.to_owned(), manifest);
+
+        if self.destination.exists() {
+            crate::bail!("{} already exists", self.destination.display());
+        }
+
+        // Copying a package into itself would keep copying the copy until the disk fills up.
+        if is_inside(&self.destination, source.path())
+            .with_context(|| "failed to resolve the source and destination paths")?
+        {
+            return Err(crate::error::Error::new_with_hint(
+                "choose a destination outside of the package",
+                anyhow::anyhow!("cannot clone {} into itself", self.source.display()),
+            ));
+        }
+
+        let name = match &self.name {
+            Some(name) => name,
+            // Default to the directory name, like `new`.
+            None => self
+                .destination
+                .file_name()
+                .and_then(|name| name.to_str())
+                .with_context(|| "expected path to be valid unicode")?,
+        };
+
+        if let Err(err) = axiom::manifest::Package::validate_name(name) {
+            return Err(crate::error::Error::new_with_hint(
+                "use `--name` to choose a different package name",
+                err,
+            ));
+        }
+
+        let destination = std::path::absolute(&self.destination)
+            .with_context(|| "failed to get the absolute path of the destination")?;
+
+        if let Some(existing) = super::rename::find_package_named(&destination, name) {
+            return Err(crate::error::Error::new_with_hint(
+                "package names must be unique, so use `--name` to choose a different name",
+                anyhow::anyhow!(
+                    "a package named '{name}' already exists at {}",
+                    existing.display()
+                ),
+            ));
+        }
+
+        // Copy everything next to the manifest too, since scripts like `pre_build` live there.
+        copy_directory(source.path(), &self.destination, &|path| {
+            path == source.server().path() || path.file_name() == Some(".git".as_ref())
+        })
+        .with_context(|| "failed to copy the package")?;
+
+        // Edit the raw manifest to preserve the user's comments and formatting.
+        let manifest_path = self.destination.join(axiom::Manifest::FILENAME);
+        let mut document = std::fs::read_to_string(&manifest_path)
+            .with_context(|| "failed to read manifest")?
+            .parse::<toml_edit::DocumentMut>()
+            .with_context(|| "failed to parse manifest")?;
+        document["package"]["name"] = toml_edit::value(name);
+        std::fs::write(&manifest_path, document.to_string())
+            .with_context(|| "failed to set the new name in the manifest")?;
+
+        let server = source.server();
+        let server_path = self.destination.join("server");
+        let server_jar_path = server_path.join("server.jar");
+        let with_world = self.with_world;
+        copy_directory(server.path(), &server_path, &|path| {
+            // The server recreates these, and a stale `session.lock` would stop it from starting.
+            let skipped = ["logs", "cache", "session.lock"];
+
+            path == server.server_jar()
+                || path
+                    .file_name()
+                    .is_some_and(|name| skipped.iter().any(|skipped| name == *skipped))
+                || (!with_world && is_world(path))
+        })
+        .with_context(|| "failed to copy the server")?;
+
+        link_server_jar(server.server_jar(), &server_jar_path)?;
+
+        if let Err(err) = crate::registry::add(&self.destination) {
+            tracing::warn!("failed to add the package to the registry: {err:#}");
+        }
+
+        if !ctx.quiet() {
+            writeln!(std::io::stderr(), "🎉 package cloned successfully").ok();
+        }
+
+        writeln!(ctx.output(), "{}: {}", "Path".bold(), destination.display()).ok();
+
+        Ok(())
+    }
+}
+
+/// Give the new server the same server JAR as the original.
+///
+/// A server JAR from the cache is linked rather than copied, since JARs are shared between
+/// packages anyway.
+fn link_server_jar(
+    source: &std::path::Path,
+    destination: &std::path::Path,
+) -> Result<(), anyhow::Error> {
+    let Ok(metadata) = source.symlink_metadata() else {
+        // The original hasn't been built yet, so there is nothing to copy.
+        return Ok(());
+    };
+
+    if !metadata.is_symlink() {
+        tracing::info!("copying server.jar, since it is not in the cache");
+        std::fs::copy(source, destination).with_context(|| "failed to copy server.jar")?;
+        return Ok(());
+    }
+
+    let target = std::fs::read_link(source).with_context(|| "failed to read server.jar link")?;
+    // Relative links are relative to the directory the link is in.
+    let target = match (target.is_relative(), source.parent()) {
+        (true, Some(parent)) => parent.join(target),
+        _ => target,
+    };
+
+    symlink::symlink_file(&target, destination).with_context(|| "failed to link server.jar")?;
+    Ok(())
+}
+
+/// Check whether `path` is (or would be) inside of the `directory`, after following links.
+///
+/// `path` doesn't need to exist yet; its closest existing ancestor is resolved instead.
+fn is_inside(path: &std::path::Path, directory: &std::path::Path) -> std::io::Result<bool> {
+    let directory = directory.canonicalize()?;
+    let path = std::path::absolute(path)?;
+
+    // At the very least, the root of the path exists.
+    let Some(existing) = path.ancestors().find(|ancestor| ancestor.exists()) else {
+        return Ok(false);
+    };
+    let rest = path
+        .strip_prefix(existing)
+        .unwrap_or(std::path::Path::new(""));
+    let path = existing.canonicalize()?.join(rest);
+
+    Ok(path.starts_with(directory))
+}
+
+/// Check whether `path` is a Minecraft world, which is any directory that contains a `level.dat`.
+pub(crate) fn is_world(path: &std::path::Path) -> bool {
+    path.join("level.dat").is_file()
+}
+
+/// Recursively copy the contents of `source` into `destination`, leaving out every path for which
+/// `skip` returns `true`.
+///
+/// Symbolic links are copied as links, rather than copying what they point to.
+fn copy_directory(
+    source: &std::path::Path,
+    destination: &std::path::Path,
+    skip: &dyn Fn(&std::path::Path) -> bool,
+) -> Result<(), anyhow::Error> {
+    std::fs::create_dir_all(destination)
+        .with_context(|| format!("failed to create {}", destination.display()))?;
+
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if skip(&path) {
+            tracing::debug!("skipping {}", path.display());
+            continue;
+        }
+
+        let target = destination.join(entry.file_name());
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            copy_directory(&path, &target, skip)?;
+        } else if file_type.is_symlink() {
+            let link = std::fs::read_link(&path)?;
+            symlink::symlink_auto(&link, &target)
+                .with_context(|| format!("failed to link {}", target.display()))?;
+        } else {
+            std::fs::copy(&path, &target)
+                .with_context(|| format!("failed to copy {}", path.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_directory() {
+        let root = tempfile::tempdir().unwrap();
+        let source = root.path().join("source");
+        std::fs::create_dir_all(source.join("world/region")).unwrap();
+        std::fs::create_dir_all(source.join("plugins")).unwrap();
+        std::fs::write(source.join("world/level.dat"), "").unwrap();
+        std::fs::write(source.join("plugins/config.yml"), "enabled: true").unwrap();
+        std::fs::write(source.join("ops.json"), "[]").unwrap();
+
+        let destination = root.path().join("destination");
+        copy_directory(&source, &destination, &is_world).unwrap();
+
+        assert!(destination.join("ops.json").exists());
+        let contents = std::fs::read_to_string(destination.join("plugins/config.yml"));
+        assert_eq!(contents.unwrap(), "enabled: true");
+        assert!(!destination.join("world").exists());
+    }
+
+    #[test]
+    fn test_is_inside() {
+        let root = tempfile::tempdir().unwrap();
+        let source = root.path().join("source");
+        std::fs::create_dir_all(&source).unwrap();
+
+        assert!(is_inside(&source.join("staging"), &source).unwrap());
+        assert!(is_inside(&source.join("a/../b/staging"), &source).unwrap());
+        assert!(is_inside(&source, &source).unwrap());
+        assert!(!is_inside(&root.path().join("staging"), &source).unwrap());
+    }
+}
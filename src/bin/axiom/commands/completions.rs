@@ -0,0 +1,31 @@
+//! This module implements the `completions` command, which generates shell completion scripts.
+
+use std::io::Write;
+
+use clap::CommandFactory;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Completions {
+    /// The shell to generate the completion script for.
+    #[arg(value_enum)]
+    pub(crate) shell: clap_complete::Shell,
+}
+
+impl crate::commands::Run for Completions {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        // Generate the script from the same definition used to parse the command-line arguments,
+        // so the completions never fall out of sync with the actual commands.
+        let mut command = crate::Args::command();
+        let name = command.get_name().to_owned();
+
+        // `generate` panics if writing fails (e.g., when piped into `head`), so write the script
+        // into a buffer first.
+        let mut buffer = Vec::new();
+        clap_complete::generate(self.shell, &mut command, name, &mut buffer);
+
+        let mut stdout = ctx.output();
+        stdout.write_all(&buffer).ok();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+//! This module implements the `delete` command, which removes a package from disk.
+
+use std::io::Write;
+
+use anyhow::Context;
+use colored::Colorize;
+
+use super::stop::Stop;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Delete {
+    /// Delete the package without prompting for confirmation.
+    #[arg(long, short = 'y')]
+    pub(crate) yes: bool,
+
+    /// Also remove the cached server JAR if no other package is using it.
+    #[arg(long)]
+    pub(crate) prune_jar: bool,
+}
+
+impl crate::commands::Run for Delete {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let package = ctx
+            .package()
+            .with_context(|| "failed to get package manifest")?;
+
+        if !self.yes && !prompt_user_to_confirm_delete(ctx, package.path()) {
+            // User was prompted to confirm the deletion interactively but they declined.
+            return Ok(());
+        }
+
+        let backend = ctx.backend()?;
+
+        if backend.is_running(&package)? {
+            tracing::info!("stopping the server");
+            Stop::run(&Stop {}, ctx)?;
+        }
+
+        backend.remove(&package)?;
+
+        // Resolve the cached JAR before the `server.jar` symlink is removed with the package.
+        let cached_jar = std::fs::read_link(package.server().server_jar()).ok();
+
+        tracing::info!("removing the package directory");
+        std::fs::remove_dir_all(package.path())
+            .with_context(|| "failed to remove package directory")?;
+
+        if let Err(err) = crate::registry::remove(package.path()) {
+            tracing::warn!("failed to remove the package from the registry: {err:#}");
+        }
+
+        if let Some(cached_jar) = cached_jar.filter(|_| self.prune_jar) {
+            prune_cached_jar(ctx, package.path(), &cached_jar)?;
+        }
+
+        if !ctx.porcelain() {
+            let mut stderr = std::io::stderr().lock();
+            writeln!(stderr, "🗑️ removed {}", package.path().display()).ok();
+        }
+
+        Ok(())
+    }
+}
+
+/// Remove the cached server JAR at `cached_jar` if no other package uses it.
+///
+/// Like `cache clean`, this checks the packages next to `package_path` and every package in the
+/// registry, counting a JAR as used if a package links to it or its manifest asks for it.
+fn prune_cached_jar(
+    ctx: &mut crate::context::Context,
+    package_path: &std::path::Path,
+    cached_jar: &std::path::Path,
+) -> Result<(), crate::error::Error> {
+    let jars = ctx.jars().with_context(|| "failed to get server JARs")?;
+
+    if !cached_jar.starts_with(&jars) {
+        tracing::warn!(
+            "not removing {} because it is not in the cache directory",
+            cached_jar.display()
+        );
+        return Ok(());
+    }
+
+    let registered =
+        crate::registry::all().with_context(|| "failed to read the package registry")?;
+    let package_path = std::path::absolute(package_path).unwrap_or(package_path.to_owned());
+    let siblings = package_path
+        .parent()
+        .map(crate::commands::list::find_packages)
+        .unwrap_or_default();
+
+    let mut packages = Vec::new();
+
+    for path in siblings.into_iter().chain(registered) {
+        let path = std::path::absolute(&path).unwrap_or(path);
+        if path != package_path && !packages.contains(&path) {
+            packages.push(path);
+        }
+    }
+
+    let in_use = cached_jar.file_name().is_some_and(|name| {
+        crate::commands::cache::jars_in_use(&packages).contains(&*name.to_string_lossy())
+    });
+
+    if in_use {
+        tracing::info!(
+            "keeping {} because it is still in use",
+            cached_jar.display()
+        );
+        return Ok(());
+    }
+
+    tracing::info!("removing {}", cached_jar.display());
+    std::fs::remove_file(cached_jar).with_context(|| "failed to remove cached server JAR")?;
+
+    Ok(())
+}
+
+/// Prompts the user to interactively confirm the deletion of the package at `path`.
+fn prompt_user_to_confirm_delete(ctx: &crate::context::Context, path: &std::path::Path) -> bool {
+    let mut stdout = ctx.output();
+    writeln!(
+        stdout,
+        "{}: {}",
+        "This will permanently delete the package".bold(),
+        path.display().to_string().underline().cyan()
+    )
+    .ok();
+    write!(
+        stdout,
+        "{} {} (y/N): ",
+        "*".cyan(),
+        "Delete and continue?".bold()
+    )
+    .ok();
+    #[rustfmt::skip]
+    stdout.flush().expect("failed to print full prompt");
+
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .expect("failed to read from stdin");
+
+    input.trim().to_lowercase() == "y"
+}
@@ -0,0 +1,276 @@
+//! This module implements the `doctor` command, which checks that everything Axiom and the
+//! current package depend on is set up correctly.
+
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+
+use colored::Colorize;
+
+use crate::error::Error;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Doctor;
+
+/// The result of a single check.
+enum Outcome {
+    /// Everything is fine. Contains a short description of what was found.
+    Pass(String),
+    /// Something is wrong, but it doesn't stop Axiom from working (e.g., the server isn't built).
+    Warn(Error),
+    /// Something is wrong that will make Axiom or the server fail.
+    Fail(Error),
+}
+
+impl crate::commands::Run for Doctor {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let package = match ctx.package_directory() {
+            Ok(directory) => Some(
+                axiom::Manifest::from_directory(&directory)
+                    .map(|manifest| axiom::Package::new(directory, manifest)),
+            ),
+            Err(_) => None,
+        };
+
+        let mut checks = vec![
+            (
+                "Java",
+                check_java(package.as_ref().and_then(|p| p.as_ref().ok())),
+            ),
+            (
+                "tmux",
+                check_tmux(package.as_ref().and_then(|p| p.as_ref().ok())),
+            ),
+            ("Data directory", check_data_directory()),
+            ("Cache directory", check_cache_directory(ctx)),
+            ("PaperMC API", check_papermc(ctx)),
+        ];
+
+        match package {
+            Some(Ok(package)) => {
+                checks.push((
+                    "Manifest",
+                    Outcome::Pass(package.manifest_path().display().to_string()),
+                ));
+                checks.push(("server.jar", check_server_jar(&package)));
+                checks.push(("EULA", check_eula(&package)));
+                checks.push((START_SCRIPT, check_start_script(&package)));
+            }
+            Some(Err(err)) => {
+                let err = Error::new(err).with_hint(|| {
+                    "run `axiom edit` to fix the manifest; the remaining package checks were skipped"
+                });
+                checks.push(("Manifest", Outcome::Fail(err)));
+            }
+            None => {}
+        }
+
+        let mut stdout = ctx.output();
+        let mut failed = 0;
+
+        for (name, outcome) in &checks {
+            let (status, message, hint) = match outcome {
+                Outcome::Pass(message) => ("pass".green(), message.to_owned(), None),
+                Outcome::Warn(err) => ("warn".yellow(), format_error(err), err.hint()),
+                Outcome::Fail(err) => {
+                    failed += 1;
+                    ("fail".red(), format_error(err), err.hint())
+                }
+            };
+
+            writeln!(stdout, "[{status}] {}: {message}", name.bold()).ok();
+
+            if let Some(hint) = hint {
+                writeln!(stdout, "       {}: {hint}", "Hint".bold().green()).ok();
+            }
+        }
+
+        if failed > 0 {
+            crate::bail!("{failed} check(s) failed");
+        }
+
+        Ok(())
+    }
+}
+
+/// Join the error and its causes into a single line.
+fn format_error(err: &Error) -> String {
+    let mut message = err.to_string();
+    let mut source = std::error::Error::source(err);
+
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+
+    message
+}
+
+/// Check that Java is installed, and new enough to run the package's version of Minecraft.
+fn check_java(package: Option<&axiom::Package>) -> Outcome {
+    let java = package.map_or(std::path::Path::new("java"), |package| {
+        package.server().java()
+    });
+
+    let installed = match axiom::java::installed_version(java) {
+        Ok(installed) => installed,
+        Err(err) if err.is_command_not_found() => {
+            return Outcome::Fail(Error::new(err).with_hint(|| super::JAVA_NOT_FOUND_HINT));
+        }
+        Err(err) => return Outcome::Warn(Error::new(err)),
+    };
+
+    if let Some(package) = package
+        && let Err(err) = super::ensure_java_compatible(package)
+    {
+        return Outcome::Fail(err);
+    }
+
+    Outcome::Pass(format!("Java {installed} ({})", java.display()))
+}
+
+/// Check that tmux is installed, unless the package runs its server with a different backend.
+fn check_tmux(package: Option<&axiom::Package>) -> Outcome {
+    let backend = package
+        .and_then(|package| package.manifest().launcher())
+        .map(|launcher| *launcher.backend())
+        .unwrap_or_default();
+
+    match (super::ensure_tmux_installed(), backend) {
+        (Ok(()), _) => Outcome::Pass("installed".to_owned()),
+        (Err(err), axiom::manifest::Backend::Tmux) => Outcome::Fail(err),
+        (Err(_), axiom::manifest::Backend::Systemd) => {
+            Outcome::Pass("skipped (the package uses the systemd backend)".to_owned())
+        }
+    }
+}
+
+/// Check that the directory the package registry is stored in can be written to.
+fn check_data_directory() -> Outcome {
+    match dirs::data_dir() {
+        Some(data_dir) => check_writable(&data_dir.join("axiom")),
+        None => Outcome::Warn(Error::new(anyhow::anyhow!("failed to get data directory"))),
+    }
+}
+
+/// Check that the directory server JARs are downloaded to can be written to.
+fn check_cache_directory(ctx: &mut crate::context::Context) -> Outcome {
+    match ctx.jars() {
+        Ok(jars) => check_writable(&jars),
+        Err(err) => Outcome::Fail(Error::new(err)),
+    }
+}
+
+/// Check that files can be created in `directory`, creating it if it doesn't exist yet.
+fn check_writable(directory: &std::path::Path) -> Outcome {
+    let probe = directory.join(format!(".axiom-doctor-{}", std::process::id()));
+    let result = std::fs::create_dir_all(directory)
+        .and_then(|()| std::fs::write(&probe, ""))
+        .and_then(|()| std::fs::remove_file(&probe));
+
+    match result {
+        Ok(()) => Outcome::Pass(directory.display().to_string()),
+        Err(err) => Outcome::Fail(Error::new_with_hint(
+            format!("check the permissions of {}", directory.display()),
+            anyhow::Error::new(err).context(format!("{} is not writable", directory.display())),
+        )),
+    }
+}
+
+fn check_papermc(ctx: &crate::context::Context) -> Outcome {
+    if ctx.offline() {
+        return Outcome::Pass("skipped (offline)".to_owned());
+    }
+
+    match axiom::paper::versions() {
+        Ok(versions) => Outcome::Pass(format!("reachable ({} versions)", versions.len())),
+        Err(err) => Outcome::Warn(Error::new_with_hint(
+            "check your internet connection, or use `--offline` to only use downloaded server JARs",
+            err,
+        )),
+    }
+}
+
+/// Check that `server.jar` points to a server JAR that exists.
+fn check_server_jar(package: &axiom::Package) -> Outcome {
+    let server_jar = package.server().server_jar();
+
+    if server_jar.exists() {
+        return match package.server().linked_build_info() {
+            Some(build_info) => Outcome::Pass(format!(
+                "Paper {} (#{})",
+                build_info.version(),
+                build_info.build()
+            )),
+            None => Outcome::Pass(server_jar.display().to_string()),
+        };
+    }
+
+    // `exists` follows the link, so a link to a missing file ends up here too.
+    match std::fs::read_link(server_jar) {
+        Ok(target) => Outcome::Fail(Error::new_with_hint(
+            "run `axiom build` to download the server JAR again",
+            anyhow::anyhow!(
+                "server.jar links to {}, which does not exist",
+                target.display()
+            ),
+        )),
+        Err(_) => Outcome::Warn(not_built("server.jar does not exist")),
+    }
+}
+
+fn check_eula(package: &axiom::Package) -> Outcome {
+    match package.server().has_accepted_eula() {
+        Ok(true) => Outcome::Pass("accepted".to_owned()),
+        Ok(false) => Outcome::Fail(Error::new_with_hint(
+            "run `axiom eula --accept` to accept it",
+            anyhow::anyhow!("the Minecraft EULA has not been accepted"),
+        )),
+        Err(_) if !package.server().eula_txt().exists() => {
+            Outcome::Warn(not_built("eula.txt does not exist"))
+        }
+        Err(err) => Outcome::Warn(Error::new(
+            anyhow::Error::new(err).context("failed to read eula.txt"),
+        )),
+    }
+}
+
+/// The name of the script `build` generates to run the server (see [`super::build`]).
+const START_SCRIPT: &str = if cfg!(unix) { "start.sh" } else { "start.bat" };
+
+#[cfg(unix)]
+fn check_start_script(package: &axiom::Package) -> Outcome {
+    let start_sh = package.server().start_sh();
+
+    let metadata = match start_sh.metadata() {
+        Ok(metadata) => metadata,
+        Err(_) => return Outcome::Warn(not_built("start.sh does not exist")),
+    };
+
+    if metadata.permissions().mode() & 0o100 == 0 {
+        return Outcome::Fail(Error::new_with_hint(
+            format!("run `chmod u+x {}` or `axiom build`", start_sh.display()),
+            anyhow::anyhow!("start.sh is not executable"),
+        ));
+    }
+
+    Outcome::Pass("executable".to_owned())
+}
+
+#[cfg(not(unix))]
+fn check_start_script(package: &axiom::Package) -> Outcome {
+    // There is no executable bit to check outside of Unix, so the script only needs to exist.
+    match super::build::start_script(package.server()).is_file() {
+        true => Outcome::Pass("exists".to_owned()),
+        false => Outcome::Warn(not_built("start.bat does not exist")),
+    }
+}
+
+/// Describe a file that is missing because the server hasn't been built yet.
+fn not_built(message: &'static str) -> Error {
+    Error::new_with_hint(
+        "run `axiom build` to set up the server",
+        anyhow::anyhow!(message),
+    )
+}
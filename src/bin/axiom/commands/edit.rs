@@ -0,0 +1,152 @@
+//! This module implements the `edit` command, which opens the package manifest in a text editor.
+
+use std::io::Write;
+
+use anyhow::Context;
+
+/// Lines starting with this prefix are added by Axiom to explain errors, and are removed again
+/// before the manifest is saved.
+const ERROR_PREFIX: &str = "# axiom: ";
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Edit;
+
+impl crate::commands::Run for Edit {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let manifest_path = ctx.package_directory()?.join(axiom::Manifest::FILENAME);
+        let original = std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("failed to read {}", manifest_path.display()))?;
+
+        // Edit a copy, so the manifest is only replaced once the changes are known to be valid.
+        // The copy is removed when `edit_file` is dropped, unless the user gives up on their
+        // changes.
+        let mut edit_file = tempfile::Builder::new()
+            .prefix("Axiom-")
+            .suffix(".toml")
+            .tempfile()
+            .context("failed to create a temporary file")?;
+        let edit_path = edit_file.path().to_owned();
+        edit_file
+            .write_all(original.as_bytes())
+            .with_context(|| format!("failed to write to {}", edit_path.display()))?;
+
+        // The contents from the last attempt, if they failed to parse.
+        let mut invalid: Option<String> = None;
+
+        let contents = loop {
+            open_editor(&edit_path)?;
+
+            let edited = std::fs::read_to_string(&edit_path)
+                .with_context(|| format!("failed to read {}", edit_path.display()))?;
+            let contents = strip_errors(&edited);
+
+            let Err(err) = contents.parse::<axiom::Manifest>() else {
+                break contents;
+            };
+            let err = anyhow::Error::new(err);
+
+            // Closing the editor without fixing anything means the user wants to give up.
+            if invalid.as_ref() == Some(&contents) {
+                edit_file.keep().ok();
+                return Err(crate::error::Error::new_with_hint(
+                    format!("your changes were kept in {}", edit_path.display()),
+                    err.context("the manifest is still invalid"),
+                ));
+            }
+
+            tracing::warn!("the manifest is invalid: {err:#}");
+            let mut annotated = format!("{ERROR_PREFIX}error: the manifest is invalid\n");
+
+            for line in format!("{err:#}").lines() {
+                annotated.push_str(&format!("{ERROR_PREFIX}{line}\n"));
+            }
+
+            annotated.push_str(&contents);
+            std::fs::write(&edit_path, annotated)
+                .with_context(|| format!("failed to write to {}", edit_path.display()))?;
+
+            invalid = Some(contents);
+        };
+
+        drop(edit_file);
+
+        let mut stderr = std::io::stderr().lock();
+
+        if contents == original {
+            if !ctx.porcelain() {
+                writeln!(stderr, "no changes were made").ok();
+            }
+            return Ok(());
+        }
+
+        std::fs::write(&manifest_path, contents)
+            .with_context(|| format!("failed to write to {}", manifest_path.display()))?;
+        if !ctx.porcelain() {
+            writeln!(stderr, "🟢 updated {}", axiom::Manifest::FILENAME).ok();
+        }
+
+        Ok(())
+    }
+}
+
+/// Open `path` in the user's preferred text editor, waiting for it to close.
+///
+/// The editor is read from the `EDITOR` environment variable, falling back to whichever common
+/// editor is installed.
+fn open_editor(path: &std::path::Path) -> Result<(), crate::error::Error> {
+    // `EDITOR` may include arguments (e.g., `code --wait`).
+    let editor = std::env::var("EDITOR")
+        .ok()
+        .filter(|editor| !editor.trim().is_empty());
+
+    let candidates = match &editor {
+        Some(editor) => vec![editor.as_str()],
+        None if cfg!(windows) => vec!["notepad"],
+        None => vec!["vi", "nano"],
+    };
+
+    for candidate in candidates {
+        let mut parts = candidate.split_whitespace();
+        let program = parts.next().expect("expected editor to be non-empty");
+
+        let status = match std::process::Command::new(program)
+            .args(parts)
+            .arg(path)
+            .status()
+        {
+            Ok(status) => status,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => {
+                return Err(anyhow::Error::new(err)
+                    .context(format!("failed to execute command '{program}'"))
+                    .into());
+            }
+        };
+
+        if !status.success() {
+            crate::bail!("editor '{program}' exited with {status}");
+        }
+
+        return Ok(());
+    }
+
+    Err(crate::error::Error::new_with_hint(
+        "set the `EDITOR` environment variable to your preferred editor",
+        anyhow::anyhow!("failed to find a text editor"),
+    ))
+}
+
+/// Remove the comments added by Axiom to explain why the manifest was invalid.
+fn strip_errors(contents: &str) -> String {
+    let mut stripped = contents
+        .lines()
+        .skip_while(|line| line.starts_with(ERROR_PREFIX.trim_end()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if contents.ends_with('\n') {
+        stripped.push('\n');
+    }
+
+    stripped
+}
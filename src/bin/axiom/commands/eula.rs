@@ -0,0 +1,63 @@
+//! This module implements the `eula` command, which checks or accepts the Minecraft EULA.
+
+use std::io::Write;
+
+use anyhow::Context;
+use colored::Colorize;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Eula {
+    /// Accept the Minecraft EULA (End User License Agreement).
+    ///
+    /// By accepting, you agree to the terms at https://aka.ms/MinecraftEULA.
+    #[arg(long)]
+    pub(crate) accept: bool,
+}
+
+impl crate::commands::Run for Eula {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let package = ctx
+            .package()
+            .with_context(|| "failed to get package manifest")?;
+        let server = package.server();
+
+        if !server.eula_txt().exists() {
+            return Err(crate::error::Error::new_with_hint(
+                "the server generates `eula.txt` the first time it runs; run `axiom build` first",
+                anyhow::anyhow!("could not find {}", server.eula_txt().display()),
+            ));
+        }
+
+        if self.accept {
+            server
+                .accept_eula()
+                .with_context(|| "failed to write to eula.txt")?;
+            if !ctx.porcelain() {
+                writeln!(std::io::stderr(), "🟢 accepted the Minecraft EULA").ok();
+            }
+            return Ok(());
+        }
+
+        let accepted = server
+            .has_accepted_eula()
+            .with_context(|| "failed to read eula.txt")?;
+        let status = match accepted {
+            true => "accepted".green(),
+            false => "not accepted".red(),
+        };
+
+        let mut stdout = ctx.output();
+        writeln!(stdout, "{}: {}", "EULA".bold(), status).ok();
+
+        if !accepted {
+            writeln!(
+                stdout,
+                "Read the EULA at {} and run `axiom eula --accept` to accept it",
+                "https://aka.ms/MinecraftEULA".underline().cyan()
+            )
+            .ok();
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+//! This module implements the `export` command, which packs the whole package into a single
+//! archive that can be moved to another machine and unpacked with `axiom import`.
+
+use std::io::Write;
+
+use anyhow::Context;
+use colored::Colorize;
+
+#[derive(clap::Args)]
+pub struct Export {
+    /// Where to write the archive. Defaults to `<name>.tar.gz` in the current directory.
+    #[arg(long, short = 'o')]
+    output: Option<std::path::PathBuf>,
+
+    /// Leave the worlds out of the archive.
+    #[arg(long)]
+    no_world: bool,
+}
+
+impl crate::commands::Run for Export {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let package = ctx
+            .package()
+            .with_context(|| "failed to get package manifest")?;
+
+        let output = match &self.output {
+            Some(output) => output.to_owned(),
+            None => std::path::PathBuf::from(format!("{}.tar.gz", package.name())),
+        };
+
+        if output.exists() {
+            return Err(crate::error::Error::new_with_hint(
+                "use `--output` to write the archive somewhere else",
+                anyhow::anyhow!("{} already exists", output.display()),
+            ));
+        }
+
+        if ctx.backend()?.is_running(&package)? {
+            tracing::warn!(
+                "the server is running, so the world may change while it is being exported"
+            );
+        }
+
+        let file = std::fs::File::create(&output)
+            .with_context(|| format!("failed to create {}", output.display()))?;
+        let mut builder = axiom::archive::TarGzBuilder::new(std::io::BufWriter::new(file));
+
+        // Everything goes inside of a directory named after the package, like `git archive`.
+        let output_path = std::path::absolute(&output).ok();
+        let no_world = self.no_world;
+        let mut entries = Vec::new();
+        let result = append_directory(
+            &mut builder,
+            package.path(),
+            package.name(),
+            &mut entries,
+            &|path| {
+                std::path::absolute(path).ok() == output_path
+                    || path.file_name() == Some(".git".as_ref())
+                    // A stale `session.lock` would stop the server from starting.
+                    || path.file_name() == Some("session.lock".as_ref())
+                    || (no_world && super::clone::is_world(path))
+            },
+        )
+        .and_then(|()| {
+            builder
+                .finish()
+                .and_then(|writer| writer.into_inner().map_err(|err| err.into_error()))
+                .with_context(|| "failed to finish writing the archive")
+        });
+
+        if let Err(err) = result {
+            std::fs::remove_file(&output).ok();
+            return Err(err.context(format!("failed to export to {}", output.display())))?;
+        }
+
+        verify_archive(&output, &entries)?;
+
+        let size = std::fs::metadata(&output)
+            .with_context(|| format!("failed to read {}", output.display()))?
+            .len();
+        let mut stdout = ctx.output();
+        writeln!(stdout, "{}: {}", "Path".bold(), output.display()).ok();
+        writeln!(stdout, "{}: {}", "Entries".bold(), entries.len()).ok();
+        writeln!(stdout, "{}: {size} bytes", "Size".bold()).ok();
+
+        Ok(())
+    }
+}
+
+/// Recursively add the contents of `directory` to the archive under `prefix`, leaving out every
+/// path for which `skip` returns `true`, and recording the name of each entry in `entries`.
+///
+/// Symbolic links are followed, so the server JAR linked from the cache ends up in the archive.
+fn append_directory<W: Write>(
+    builder: &mut axiom::archive::TarGzBuilder<W>,
+    directory: &std::path::Path,
+    prefix: &str,
+    entries: &mut Vec<String>,
+    skip: &dyn Fn(&std::path::Path) -> bool,
+) -> Result<(), anyhow::Error> {
+    builder
+        .append_dir(prefix)
+        .with_context(|| format!("failed to add {}", directory.display()))?;
+    entries.push(format!("{prefix}/"));
+
+    let mut children = std::fs::read_dir(directory)
+        .with_context(|| format!("failed to read {}", directory.display()))?
+        .collect::<Result<Vec<_>, _>>()?;
+    // Keep the order of the entries the same between exports.
+    children.sort_by_key(|entry| entry.file_name());
+
+    for entry in children {
+        let path = entry.path();
+
+        if skip(&path) {
+            tracing::debug!("skipping {}", path.display());
+            continue;
+        }
+
+        let file_name = entry.file_name();
+        let name = format!("{prefix}/{}", file_name.to_string_lossy());
+
+        if path.is_dir() {
+            append_directory(builder, &path, &name, entries, skip)?;
+        } else {
+            builder
+                .append_file(&name, &path)
+                .with_context(|| format!("failed to add {}", path.display()))?;
+            entries.push(name);
+        }
+    }
+
+    Ok(())
+}
+
+/// Read the archive back, making sure it contains exactly the `expected` entries.
+fn verify_archive(output: &std::path::Path, expected: &[String]) -> Result<(), anyhow::Error> {
+    let file = std::fs::File::open(output)
+        .with_context(|| format!("failed to open {}", output.display()))?;
+    let actual = axiom::archive::list(std::io::BufReader::new(file), axiom::archive::Format::TarGz)
+        .with_context(|| format!("failed to read back {}", output.display()))?;
+
+    if actual != expected {
+        anyhow::bail!(
+            "{} does not contain what was written to it ({} of {} entries)",
+            output.display(),
+            actual.len(),
+            expected.len()
+        );
+    }
+
+    Ok(())
+}
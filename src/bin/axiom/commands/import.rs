@@ -0,0 +1,191 @@
+//! This module implements the `import` command, which unpacks a package created by
+//! `axiom export`.
+
+use std::io::{Seek, Write};
+
+use anyhow::Context;
+use colored::Colorize;
+
+#[derive(clap::Args)]
+pub struct Import {
+    /// Path to the archive created by `axiom export`.
+    archive: std::path::PathBuf,
+
+    /// Where to put the package. Defaults to a directory named after the package, inside of the
+    /// current directory.
+    path: Option<std::path::PathBuf>,
+}
+
+impl crate::commands::Run for Import {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let format = self
+            .archive
+            .to_str()
+            .and_then(axiom::archive::Format::from_name)
+            .ok_or_else(|| {
+                crate::error::Error::new_with_hint(
+                    "expected the archive to end with .zip, .tar.gz, or .tgz",
+                    anyhow::anyhow!("unsupported archive format: {}", self.archive.display()),
+                )
+            })?;
+        let mut file = std::fs::File::open(&self.archive)
+            .map(std::io::BufReader::new)
+            .with_context(|| format!("failed to open {}", self.archive.display()))?;
+
+        let entries = axiom::archive::list(&mut file, format)
+            .with_context(|| format!("failed to read {}", self.archive.display()))?;
+        file.rewind()
+            .with_context(|| format!("failed to read {}", self.archive.display()))?;
+        let top = top_directory(&entries).ok_or_else(|| {
+            crate::error::Error::new_with_hint(
+                "use `axiom export` to create the archive",
+                anyhow::anyhow!("expected everything in the archive to be inside one directory"),
+            )
+        })?;
+
+        let destination = match &self.path {
+            Some(path) => path.to_owned(),
+            None => std::path::PathBuf::from(top),
+        };
+
+        if destination.exists() {
+            crate::bail!("{} already exists", destination.display());
+        }
+
+        let destination = std::path::absolute(&destination)
+            .with_context(|| "failed to get the absolute path of the destination")?;
+        let parent = destination
+            .parent()
+            .with_context(|| "failed to get the destination's parent directory")?;
+
+        // Unpack next to the destination, so the package can be moved into place in one step.
+        let staging = parent.join(format!(".axiom-import-{}", std::process::id()));
+        let result = axiom::archive::extract(&mut file, format, &staging)
+            .with_context(|| format!("failed to unpack {}", self.archive.display()))
+            .and_then(|()| {
+                std::fs::rename(staging.join(top), &destination).with_context(|| {
+                    format!("failed to move the package to {}", destination.display())
+                })
+            });
+        std::fs::remove_dir_all(&staging).ok();
+        result?;
+
+        let manifest = axiom::Manifest::from_directory(&destination).map_err(|err| {
+            crate::error::Error::new(err).with_hint(|| "use `axiom export` to create the archive")
+        })?;
+        let package = axiom::Package::new(destination.to_owned(), manifest);
+
+        if let Err(err) = link_cached_jar(ctx, &package) {
+            tracing::warn!("failed to move server.jar into the cache: {err:#}");
+        }
+
+        if let Some(existing) = super::rename::find_package_named(&destination, package.name()) {
+            tracing::warn!(
+                "a package named '{}' already exists at {}; use `axiom rename` to give one of \
+                them a different name",
+                package.name(),
+                existing.display()
+            );
+        }
+
+        if let Err(err) = crate::registry::add(&destination) {
+            tracing::warn!("failed to add the package to the registry: {err:#}");
+        }
+
+        if !ctx.quiet() {
+            writeln!(std::io::stderr(), "🎉 package imported successfully").ok();
+        }
+
+        writeln!(ctx.output(), "{}: {}", "Path".bold(), destination.display()).ok();
+
+        Ok(())
+    }
+}
+
+/// Get the name of the directory every entry is inside of, if there is exactly one.
+fn top_directory(entries: &[String]) -> Option<&str> {
+    let mut top = None;
+
+    for entry in entries {
+        let (directory, _) = entry.split_once('/')?;
+
+        match top {
+            None if !directory.is_empty() && directory != "." && directory != ".." => {
+                top = Some(directory);
+            }
+            Some(top) if top == directory => {}
+            _ => return None,
+        }
+    }
+
+    top
+}
+
+/// Replace the package's `server.jar` with a link to the same JAR in the cache, if the cache
+/// already has it.
+///
+/// `export` copies the JAR into the archive, since the cache it was linked from stays behind. The
+/// JAR in the archive is never moved into the cache: the cache is trusted to contain the builds
+/// PaperMC published (`update` skips verifying a cached JAR), and nothing vouches for an archive.
+fn link_cached_jar(
+    ctx: &mut crate::context::Context,
+    package: &axiom::Package,
+) -> Result<(), anyhow::Error> {
+    let server_jar = package.server().server_jar();
+    let Ok(metadata) = server_jar.symlink_metadata() else {
+        // The server wasn't built when it was exported.
+        return Ok(());
+    };
+
+    let server = package.manifest().server();
+    let download_name = format!("paper-{}-{}.jar", server.version(), server.build());
+    let cached_jar = ctx.jars()?.join(&download_name);
+
+    match cached_jar.metadata() {
+        Ok(cached) if cached.len() == metadata.len() => {
+            std::fs::remove_file(server_jar).with_context(|| "failed to remove server.jar")?;
+        }
+        Ok(_) => {
+            tracing::warn!(
+                "server.jar is not the same as {download_name} in the cache; keeping it"
+            );
+            return Ok(());
+        }
+        Err(_) => {
+            tracing::debug!("{download_name} is not in the cache; keeping server.jar");
+            return Ok(());
+        }
+    }
+
+    tracing::info!("linking server.jar to {}", cached_jar.display());
+    symlink::symlink_file(&cached_jar, server_jar).with_context(|| "failed to link server.jar")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_directory() {
+        let entries = |names: &[&str]| {
+            names
+                .iter()
+                .map(|&name| name.to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            top_directory(&entries(&["example/", "example/Axiom.toml"])),
+            Some("example")
+        );
+        assert_eq!(
+            top_directory(&entries(&["example/", "other/Axiom.toml"])),
+            None
+        );
+        assert_eq!(top_directory(&entries(&["Axiom.toml"])), None);
+        assert_eq!(top_directory(&entries(&["../example/"])), None);
+        assert_eq!(top_directory(&[]), None);
+    }
+}
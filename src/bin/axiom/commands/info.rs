@@ -0,0 +1,76 @@
+//! This module implements the `info` command, which summarizes the current package.
+
+use std::io::Write;
+
+use anyhow::Context;
+use colored::Colorize;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Info;
+
+impl crate::commands::Run for Info {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let package = ctx
+            .package()
+            .with_context(|| "failed to get package manifest")?;
+        let server = package.server();
+
+        let configured = format!(
+            "{} (#{})",
+            package.manifest().server().version(),
+            package.manifest().server().build()
+        );
+
+        let installed = if server.server_jar().exists() {
+            match server.installed_build_info() {
+                Ok(build_info) => format!("{} (#{})", build_info.version(), build_info.build()),
+                Err(err) => {
+                    tracing::warn!("failed to get build information for current server JAR: {err}");
+                    "unknown".to_owned()
+                }
+            }
+        } else {
+            "not built".to_owned()
+        };
+
+        let eula = match server.has_accepted_eula() {
+            Ok(true) => "accepted",
+            Ok(false) => "not accepted",
+            Err(_) if !server.eula_txt().exists() => "not built",
+            Err(_) => "unknown",
+        };
+
+        let running = match ctx
+            .backend()
+            .and_then(|backend| backend.is_running(&package))
+        {
+            Ok(true) => "yes",
+            Ok(false) => "no",
+            Err(err) => {
+                tracing::warn!("failed to check if the server is running: {err}");
+                "unknown"
+            }
+        };
+
+        let start_command =
+            super::build::start_command(package.manifest().launcher(), server.java());
+
+        let mut stdout = ctx.output();
+        writeln!(stdout, "{}: {}", "Package".bold(), package.name()).ok();
+        writeln!(
+            stdout,
+            "{}: {}",
+            "Package Version".bold(),
+            package.version()
+        )
+        .ok();
+        writeln!(stdout, "{}: {}", "Path".bold(), package.path().display()).ok();
+        writeln!(stdout, "{}: {}", "Configured Version".bold(), configured).ok();
+        writeln!(stdout, "{}: {}", "Installed Version".bold(), installed).ok();
+        writeln!(stdout, "{}: {}", "EULA".bold(), eula).ok();
+        writeln!(stdout, "{}: {}", "Running".bold(), running).ok();
+        writeln!(stdout, "{}: {}", "Start Command".bold(), start_command).ok();
+
+        Ok(())
+    }
+}
@@ -1,62 +1,214 @@
-//! This module implements the `list` command, which displays active Minecraft servers.
+//! This module implements the `list` command, which displays Minecraft servers and whether they
+//! are currently running.
 
 use std::io::{BufRead, Write};
 
 use anyhow::Context;
 
-use super::{TMUX_SERVER_NAME, TMUX_SESSION_NAME};
-
 #[derive(clap::Args)]
-pub struct List;
+pub struct List {
+    /// The directory containing the packages. Defaults to the current directory.
+    ///
+    /// The directory itself and each of its immediate subdirectories are checked for packages.
+    /// Servers running in tmux and packages created with `new` are always listed, even if they
+    /// live somewhere else.
+    #[arg(long)]
+    path: Option<std::path::PathBuf>,
+
+    /// Only show servers that are currently running.
+    #[arg(long)]
+    running: bool,
+
+    /// How to display the list of servers.
+    #[arg(long, value_enum, default_value_t = Format::Human)]
+    format: Format,
+}
+
+/// Describes how the list of servers should be displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum Format {
+    /// One server per line, meant to be read by people.
+    Human,
+    /// A JSON array of objects, meant to be read by other programs.
+    Json,
+}
+
+/// Describes a single server in the list.
+#[derive(Debug, serde::Serialize)]
+struct Entry {
+    name: String,
+    path: std::path::PathBuf,
+    version: Option<String>,
+    build: Option<i64>,
+    running: bool,
+}
 
 impl crate::commands::Run for List {
-    fn run(&self, _: &mut crate::context::Context) -> Result<(), crate::error::Error> {
-        let output = std::process::Command::new("tmux")
-            .args([
-                "-L",
-                TMUX_SERVER_NAME,
-                "list-panes",
-                "-t",
-                &format!("={}", TMUX_SESSION_NAME),
-                "-s",
-                "-F",
-                "#{pane_current_path}",
-            ])
-            .output()
-            .with_context(|| "failed to execute command 'tmux'")?;
-
-        let mut stdout = std::io::stdout().lock();
-
-        for line in output.stdout.lines() {
-            let line = line.with_context(|| "failed to read line")?;
-            // The pane's path should end up in the package's server directory, so `parent()`
-            // should lead to the package's path.
-            let package_path = std::path::Path::new(&line)
-                .parent()
-                .expect("expected tmux to return an absolute path");
-            let manifest = axiom::Manifest::from_directory(package_path)
-                .with_context(|| "failed to get package manifest")?;
-            let package = axiom::Package::new(package_path.to_path_buf(), manifest);
-
-            // XXX: This is noticeably slow. maybe follow the server.jar symlink back to its
-            // original and parse the file name instead, falling back to `build_info()` only if
-            // we need to.
-            let build_info = package
-                .server()
-                .build_info()
-                .with_context(|| "failed to get build information for current server JAR")?;
-
-            writeln!(
-                stdout,
-                "{package_name} {version}#{build} {package_path}",
-                package_name = package.name(),
-                version = build_info.version(),
-                build = build_info.build(),
-                package_path = package.path().display()
-            )
-            .ok();
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        // Servers running in tmux can be found even if they aren't registered. Packages that use
+        // a different backend are still found through the directory and the registry.
+        let mut package_paths = match axiom::tmux::ensure_installed() {
+            Ok(()) => running_servers(&ctx.tmux_session())?
+                .into_iter()
+                .map(|(_, path)| path)
+                .collect::<Vec<_>>(),
+            Err(_) => Vec::new(),
+        };
+
+        let directory = match &self.path {
+            Some(path) => path.to_owned(),
+            None => ctx.directory()?,
+        };
+
+        let registered = crate::registry::all().unwrap_or_else(|err| {
+            tracing::warn!("failed to read the package registry: {err:#}");
+            Vec::new()
+        });
+
+        for path in find_packages(&directory).into_iter().chain(registered) {
+            let path = std::path::absolute(&path).unwrap_or(path);
+
+            if !package_paths.contains(&path) {
+                package_paths.push(path);
+            }
+        }
+
+        let mut entries = Vec::new();
+
+        for package_path in package_paths {
+            let manifest = match axiom::Manifest::from_directory(&package_path) {
+                Ok(manifest) => manifest,
+                Err(err) => {
+                    tracing::warn!("skipping {}: {err}", package_path.display());
+                    continue;
+                }
+            };
+            let package = axiom::Package::new(package_path, manifest);
+
+            let running = ctx
+                .backend_for(&package)
+                .and_then(|backend| backend.is_running(&package))
+                .unwrap_or_else(|err| {
+                    tracing::warn!(
+                        "failed to check whether {} is running: {err:#}",
+                        package.name()
+                    );
+                    false
+                });
+
+            if self.running && !running {
+                continue;
+            }
+
+            // Packages that haven't been built yet don't have a `server.jar`.
+            let build_info = match package.server().installed_build_info() {
+                Ok(build_info) => Some(build_info),
+                Err(axiom::package::ServerBuildInfoError::ServerJarNotFound { .. }) => None,
+                Err(err) => {
+                    tracing::warn!("failed to get the version of {}: {err}", package.name());
+                    None
+                }
+            };
+
+            entries.push(Entry {
+                name: package.name().to_owned(),
+                path: package.path().to_path_buf(),
+                version: build_info.as_ref().map(|info| info.version().to_owned()),
+                build: build_info.as_ref().map(|info| info.build()),
+                running,
+            });
+        }
+
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut stdout = ctx.output();
+
+        let format = match ctx.json() {
+            true => Format::Json,
+            false => self.format,
+        };
+
+        match format {
+            Format::Human => {
+                for entry in entries.iter() {
+                    let marker = if entry.running { "🟢" } else { "🔴" };
+                    let version = match (&entry.version, entry.build) {
+                        (Some(version), Some(build)) => format!("{version}#{build}"),
+                        _ => "unknown".to_owned(),
+                    };
+
+                    writeln!(
+                        stdout,
+                        "{marker} {name} {version} {path}",
+                        name = entry.name,
+                        path = entry.path.display()
+                    )
+                    .ok();
+                }
+            }
+            Format::Json => {
+                let json = serde_json::to_string(&entries)
+                    .with_context(|| "failed to serialize list of servers")?;
+                writeln!(stdout, "{json}").ok();
+            }
         }
 
         Ok(())
     }
 }
+
+/// Get the name and package path of every server running in the tmux `session`.
+pub(crate) fn running_servers(
+    session: &axiom::tmux::Session,
+) -> Result<Vec<(String, std::path::PathBuf)>, anyhow::Error> {
+    // Get every window in the session at once, rather than querying tmux once per server.
+    let output = std::process::Command::new("tmux")
+        .args([
+            "-L",
+            session.server(),
+            "list-panes",
+            "-t",
+            &session.target(),
+            "-s",
+            "-F",
+            "#{window_name}\t#{pane_current_path}",
+        ])
+        .output()
+        .with_context(|| "failed to execute command 'tmux'")?;
+
+    let mut panes = Vec::new();
+
+    for line in output.stdout.lines() {
+        let line = line.with_context(|| "failed to read line")?;
+        let (window_name, path) = line
+            .split_once('\t')
+            .with_context(|| "unexpected output from tmux")?;
+
+        // The pane's path should end up in the package's server directory, so `parent()`
+        // should lead to the package's path.
+        let Some(package_path) = std::path::Path::new(path)
+            .parent()
+            .filter(|parent| parent.is_absolute())
+        else {
+            tracing::debug!("skipping window '{window_name}' with unexpected path '{path}'");
+            continue;
+        };
+        panes.push((window_name.to_owned(), package_path.to_path_buf()));
+    }
+
+    Ok(panes)
+}
+
+/// Find the packages in `directory`, including `directory` itself.
+pub(crate) fn find_packages(directory: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let subdirectories = std::fs::read_dir(directory)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path());
+
+    std::iter::once(directory.to_path_buf())
+        .chain(subdirectories)
+        .filter(|path| path.join(axiom::Manifest::FILENAME).is_file())
+        .collect()
+}
@@ -1,4 +1,5 @@
 use std::io::Write;
+use std::time::Duration;
 
 use anyhow::Context;
 
@@ -15,13 +16,79 @@ pub struct New {
     #[clap(long)]
     server: Option<std::path::PathBuf>,
 
+    /// URL of an archive (.zip or .tar.gz) containing an existing Minecraft server to download.
+    #[clap(long, conflicts_with = "server")]
+    from_url: Option<String>,
+
+    /// The maximum number of seconds to wait for the archive to download.
+    #[clap(long, default_value = "120", requires = "from_url")]
+    timeout: u64,
+
     /// Path to the existing Minecraft server JAR file.
     #[clap(long)]
     jar: Option<std::path::PathBuf>,
 
     /// Initialize a new git repository.
+    ///
+    /// By default, the whole `server` directory is ignored, so only the manifest is tracked.
     #[clap(long)]
     git: bool,
+
+    /// Track the server's configuration files (e.g., `ops.json`, `bukkit.yml`) in git, and only
+    /// ignore the parts that can be regenerated or are too large to version (worlds, logs, caches,
+    /// and the server JAR).
+    ///
+    /// Note that some of the tracked files are also rewritten by `axiom build` (e.g.,
+    /// `server.properties`), and others may contain sensitive data (e.g., IP addresses in
+    /// `banned-ips.json`), so review what you commit.
+    #[clap(long, requires = "git")]
+    git_track_config: bool,
+
+    /// Start from a predefined configuration, which fills in the `[launcher]` and `[properties]`
+    /// sections of the manifest.
+    #[clap(long, value_enum)]
+    template: Option<Template>,
+}
+
+/// Describes a predefined configuration for a new package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum Template {
+    /// A plain survival server.
+    Vanilla,
+    /// A whitelisted survival server with PvP and Aikar's flags.
+    Smp,
+    /// Creative mode for everyone, with no hostile mobs.
+    Creative,
+}
+
+impl Template {
+    /// Get the TOML tables this template adds to the manifest.
+    fn contents(&self) -> &'static str {
+        match self {
+            Self::Vanilla => include_str!("../templates/vanilla.toml"),
+            Self::Smp => include_str!("../templates/smp.toml"),
+            Self::Creative => include_str!("../templates/creative.toml"),
+        }
+    }
+
+    /// Copy the template's tables into `manifest`.
+    fn apply(&self, manifest: &mut toml_edit::DocumentMut) {
+        let template = self
+            .contents()
+            .parse::<toml_edit::DocumentMut>()
+            .expect("expected built-in template to be valid TOML");
+
+        for (key, item) in template.iter() {
+            let mut item = item.clone();
+
+            // Keep a blank line between each table, like the rest of the manifest.
+            if let Some(table) = item.as_table_mut() {
+                table.decor_mut().set_prefix("\n");
+            }
+
+            manifest[key] = item;
+        }
+    }
 }
 
 impl crate::commands::Run for New {
@@ -30,6 +97,39 @@ impl crate::commands::Run for New {
             crate::bail!("cannot run the `new` command on an existing directory");
         }
 
+        let name = match &self.name {
+            Some(name) => name,
+            // Default to the directory name.
+            None => self
+                .path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .with_context(|| "expected path to be valid unicode")?,
+        };
+
+        // Check the name before touching the filesystem, otherwise the package would be created
+        // with a manifest that can't be loaded.
+        if let Err(err) = axiom::manifest::Package::validate_name(name) {
+            return Err(crate::error::Error::new_with_hint(
+                "use `--name` to choose a different package name",
+                err,
+            ));
+        }
+
+        // Check the URL before touching the filesystem too.
+        let archive_format = match &self.from_url {
+            Some(url) => match axiom::archive::Format::from_name(url) {
+                Some(format) => Some(format),
+                None => {
+                    return Err(crate::error::Error::new_with_hint(
+                        "expected the URL to end with .zip, .tar.gz, or .tgz",
+                        anyhow::anyhow!("unsupported archive format: {url}"),
+                    ));
+                }
+            },
+            None => None,
+        };
+
         std::fs::create_dir_all(&self.path)
             .with_context(|| "failed to create package directory")?;
 
@@ -38,26 +138,75 @@ impl crate::commands::Run for New {
             // If the user has an existing server already, rename it.
             std::fs::rename(existing_server, &server_path)
                 .with_context(|| "failed to move existing Minecraft server")?;
+        } else if let (Some(url), Some(format)) = (&self.from_url, archive_format) {
+            let timeout = Duration::from_secs(self.timeout);
+
+            if let Err(err) = download_server(url, format, timeout, &server_path) {
+                // Nothing else has been added yet, so this leaves things as they were and the
+                // command can be retried.
+                std::fs::remove_dir_all(&self.path).ok();
+                return Err(err.into());
+            }
         } else {
             // Otherwise, create a new empty directory.
             std::fs::create_dir_all(&server_path)
                 .with_context(|| "failed to create new 'server' directory")?;
         }
 
+        // JARs downloaded from PaperMC keep their original name, which tells us the version
+        // without having to run the JAR.
+        let named_build_info = self
+            .jar
+            .as_ref()
+            .and_then(|jar| jar.file_name()?.to_str())
+            .and_then(axiom::package::ServerBuildInfo::from_download_name);
+
         let server_jar_path = server_path.join("server.jar");
         if let Some(existing_jar) = &self.jar {
-            std::fs::rename(existing_jar, &server_jar_path)
-                .with_context(|| "failed to move existing server JAR")?;
+            let cached_jar = match named_build_info {
+                Some(_) => find_cached_jar(ctx, existing_jar)?,
+                None => None,
+            };
+
+            match cached_jar {
+                Some(cached_jar) => {
+                    // Don't delete the cached JAR if that's what was passed to `--jar`.
+                    let same_file =
+                        cached_jar.canonicalize().ok() == existing_jar.canonicalize().ok();
+
+                    tracing::info!("linking server.jar to {}", cached_jar.display());
+                    symlink::symlink_file(&cached_jar, &server_jar_path)
+                        .with_context(|| "failed to link server.jar")?;
+
+                    if !same_file {
+                        std::fs::remove_file(existing_jar)
+                            .with_context(|| "failed to remove existing server JAR")?;
+                    }
+                }
+                None => std::fs::rename(existing_jar, &server_jar_path)
+                    .with_context(|| "failed to move existing server JAR")?,
+            }
         };
 
         let server = axiom::package::Server::new(server_path, server_jar_path);
 
         // Get the version and build number to insert into the manifest.
-        let (version, build) = if self.jar.is_some() {
+        let (version, build) = if let Some(build_info) = named_build_info {
+            (build_info.version().to_owned(), build_info.build())
+        } else if self.jar.is_some() {
             // Get the version from the existing server JAR.
-            let build_info = server
-                .build_info()
-                .with_context(|| "failed to get build info from the existing server JAR")?;
+            let build_info = server.build_info().map_err(|err| {
+                let not_found = err.is_command_not_found();
+                let err = crate::error::Error::new(
+                    anyhow::Error::new(err)
+                        .context("failed to get build info from the existing server JAR"),
+                );
+
+                match not_found {
+                    true => err.with_hint(|| super::JAVA_NOT_FOUND_HINT),
+                    false => err,
+                }
+            })?;
             let version = build_info.version().to_owned();
             let build = build_info.build();
 
@@ -70,10 +219,11 @@ impl crate::commands::Run for New {
                 .with_context(|| "failed to get supported Minecraft versions from PaperMC")?
                 .clone();
 
-            let latest_build = versions
+            let latest_version = versions
                 .last()
-                .with_context(|| "no supported Minecraft versions found")?
-                .builds()
+                .with_context(|| "no supported Minecraft versions found")?;
+            let latest_build = ctx
+                .builds(latest_version)
                 .with_context(|| "failed to get builds for selected version")?
                 .pop()
                 .with_context(|| "no builds found")?;
@@ -87,28 +237,22 @@ impl crate::commands::Run for New {
         // Create the `Axiom.toml` file.
         let mut manifest = toml_edit::DocumentMut::new();
         manifest["package"] = toml_edit::Item::Table(toml_edit::Table::new());
-        manifest["package"]["name"] = {
-            let name = match &self.name {
-                Some(name) => name,
-                // Default to the directory name.
-                None => self
-                    .path
-                    .file_name()
-                    .and_then(|name| name.to_str())
-                    .with_context(|| "expected path to be valid unicode")?,
-            };
-
-            toml_edit::value(name)
-        };
+        manifest["package"]["name"] = toml_edit::value(name);
         manifest["package"]["version"] = toml_edit::value("0.1.0");
         manifest["server"] = toml_edit::Item::Table(toml_edit::Table::new());
         manifest["server"]["version"] = toml_edit::value(version);
         manifest["server"]["build"] = toml_edit::value(build);
 
+        if let Some(template) = self.template {
+            template.apply(&mut manifest);
+        }
+
         // If a `server.properties` file exists in `./server`, copy the properties into Axiom.toml.
         let server_properties = server.server_properties();
         if server_properties.exists() {
-            manifest["properties"] = toml_edit::Item::Table(toml_edit::Table::new());
+            if !manifest.contains_key("properties") {
+                manifest["properties"] = toml_edit::Item::Table(toml_edit::Table::new());
+            }
 
             tracing::warn!(
                 "deserializing the `server.properties` file is currently unimplemented! \
@@ -117,14 +261,29 @@ impl crate::commands::Run for New {
         }
 
         let manifest_path = self.path.join("Axiom.toml");
-        std::fs::write(&manifest_path, manifest.to_string())
+        let contents = manifest.to_string();
+        super::validate_manifest(&manifest_path, &contents)?;
+
+        std::fs::write(&manifest_path, contents)
             .with_context(|| "failed to create Axiom.toml file")?;
 
+        if let Err(err) = crate::registry::add(&self.path) {
+            tracing::warn!("failed to add the package to the registry: {err:#}");
+        }
+
         if self.git {
             let gitignore = self.path.join(".gitignore");
-            let ignore_items = ["/server"];
+            let ignore_items: &[&str] = match self.git_track_config {
+                true => &[
+                    "/server/world*",
+                    "/server/server.jar",
+                    "/server/logs",
+                    "/server/cache",
+                ],
+                false => &["/server", "/server/logs"],
+            };
 
-            std::fs::write(&gitignore, ignore_items.join("\n"))
+            std::fs::write(&gitignore, ignore_items.join("\n") + "\n")
                 .with_context(|| "failed to create .gitignore file")?;
 
             if let Err(err) = initialize_git(&self.path) {
@@ -132,30 +291,152 @@ impl crate::commands::Run for New {
             }
         }
 
-        let mut stderr = std::io::stderr().lock();
-        // TODO: Provide better output:
-        // (See start.rs and stop.rs for examples)
-        writeln!(stderr, "🎉 package created successfully").ok();
+        if !ctx.porcelain() {
+            let mut stderr = std::io::stderr().lock();
+            // TODO: Provide better output:
+            // (See start.rs and stop.rs for examples)
+            writeln!(stderr, "🎉 package created successfully").ok();
+        }
 
         Ok(())
     }
 }
 
+/// Download the archive at `url` and unpack it into `server_path`.
+fn download_server(
+    url: &str,
+    format: axiom::archive::Format,
+    timeout: Duration,
+    server_path: &std::path::Path,
+) -> Result<(), anyhow::Error> {
+    // Download next to the server directory, so the archive doesn't end up inside the server.
+    let download = server_path.with_file_name(".axiom-download");
+
+    tracing::info!("downloading {url}");
+    let result = axiom::archive::fetch(url, timeout, &download)
+        .with_context(|| format!("failed to download {url}"))
+        .and_then(|()| {
+            std::fs::create_dir_all(server_path)
+                .with_context(|| "failed to create new 'server' directory")?;
+            let file = std::fs::File::open(&download)
+                .with_context(|| format!("failed to open {}", download.display()))?;
+
+            axiom::archive::extract(std::io::BufReader::new(file), format, server_path)
+                .with_context(|| "failed to extract the downloaded archive")
+        });
+    std::fs::remove_file(&download).ok();
+    result?;
+
+    // Archives are often made from the server directory itself, which leaves everything nested
+    // one level too deep.
+    let entries = std::fs::read_dir(server_path)?.collect::<Result<Vec<_>, _>>()?;
+    if let [entry] = entries.as_slice()
+        && entry.file_type()?.is_dir()
+    {
+        // Rename it first, in case it contains a file with the same name.
+        let nested = server_path.join(".axiom-unpack");
+        std::fs::rename(entry.path(), &nested)?;
+
+        for child in std::fs::read_dir(&nested)? {
+            let child = child?;
+            std::fs::rename(child.path(), server_path.join(child.file_name()))
+                .with_context(|| "failed to move files out of the archive's top directory")?;
+        }
+
+        std::fs::remove_dir(&nested)?;
+    }
+
+    Ok(())
+}
+
+/// Find the copy of `jar` that Axiom already downloaded, if there is one.
+///
+/// The cached JAR is only used if it has the same name and size as `jar`.
+fn find_cached_jar(
+    ctx: &mut crate::context::Context,
+    jar: &std::path::Path,
+) -> Result<Option<std::path::PathBuf>, anyhow::Error> {
+    let Some(file_name) = jar.file_name() else {
+        return Ok(None);
+    };
+
+    let cached_jar = ctx.jars()?.join(file_name);
+    let (Ok(cached), Ok(imported)) = (cached_jar.metadata(), jar.metadata()) else {
+        return Ok(None);
+    };
+
+    match cached.len() == imported.len() {
+        true => Ok(Some(cached_jar)),
+        false => Ok(None),
+    }
+}
+
 fn initialize_git<P>(path: P) -> Result<(), anyhow::Error>
 where
     P: AsRef<std::path::Path>,
 {
+    let path = path.as_ref();
+    git(path, &["init"]).with_context(|| "failed to initialize git")?;
+
+    // Committing requires an identity; don't make one up on the user's behalf.
+    let has_identity = ["user.name", "user.email"]
+        .iter()
+        .all(|key| git(path, &["config", key]).is_ok());
+
+    if !has_identity {
+        tracing::warn!(
+            "skipping the initial commit because git's `user.name` and `user.email` are not set"
+        );
+        return Ok(());
+    }
+
+    git(path, &["add", "-A"]).with_context(|| "failed to stage files")?;
+    git(path, &["commit", "-m", "Initial commit"])
+        .with_context(|| "failed to create the initial commit")?;
+
+    Ok(())
+}
+
+/// Run `git` with `args` inside of `path`, failing if it exits unsuccessfully.
+fn git(path: &std::path::Path, args: &[&str]) -> Result<(), anyhow::Error> {
     let status = std::process::Command::new("git")
-        .current_dir(path.as_ref())
-        .arg("init")
+        .current_dir(path)
+        .args(args)
         .stdout(std::process::Stdio::null())
         .stderr(std::process::Stdio::null())
         .status()
         .with_context(|| "failed to execute command 'git'")?;
 
     if !status.success() {
-        anyhow::bail!("failed to initialize git");
+        anyhow::bail!("'git {}' exited with {status}", args.join(" "));
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use clap::ValueEnum;
+
+    #[test]
+    fn test_templates() {
+        for template in Template::value_variants() {
+            let mut manifest = crate::testing::MANIFEST
+                .parse::<toml_edit::DocumentMut>()
+                .unwrap();
+            template.apply(&mut manifest);
+
+            let contents = manifest.to_string();
+            assert!(axiom::Manifest::validate(&contents).is_ok(), "{template:?}");
+
+            let manifest = contents.parse::<axiom::Manifest>();
+            assert!(manifest.is_ok(), "{template:?}: {:?}", manifest.err());
+
+            let manifest = manifest.unwrap();
+            assert!(manifest.launcher().is_some(), "{template:?}");
+            assert!(manifest.properties().is_some(), "{template:?}");
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! This module implements the `plugin` command, which manages the plugins declared in the
+//! manifest's `[plugins]` table.
+
+mod install;
+mod list;
+
+use crate::commands::Run;
+
+#[derive(clap::Args)]
+pub struct Plugin {
+    #[command(subcommand)]
+    command: PluginCommand,
+}
+
+#[derive(clap::Subcommand)]
+enum PluginCommand {
+    /// Download every plugin declared in the manifest into the server's `plugins` directory.
+    Install(install::Install),
+
+    /// Show the installed plugins and whether they are declared in the manifest.
+    List(list::List),
+}
+
+impl Run for Plugin {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        match &self.command {
+            PluginCommand::Install(handler) => handler.run(ctx),
+            PluginCommand::List(handler) => handler.run(ctx),
+        }
+    }
+}
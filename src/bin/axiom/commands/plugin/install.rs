@@ -0,0 +1,83 @@
+use std::io::Write;
+
+use anyhow::Context;
+
+#[derive(clap::Args)]
+pub struct Install {
+    /// Seconds to wait before failing to download a plugin.
+    #[arg(long, short = 't', default_value = "120")]
+    timeout: u64,
+}
+
+impl crate::commands::Run for Install {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let package = ctx
+            .package()
+            .with_context(|| "failed to get package manifest")?;
+
+        let Some(plugins) = package.manifest().plugins() else {
+            tracing::info!("no plugins declared in {}", axiom::Manifest::FILENAME);
+            return Ok(());
+        };
+
+        let plugins_dir = package.server().plugins();
+        std::fs::create_dir_all(plugins_dir)
+            .with_context(|| "failed to create 'plugins' directory")?;
+
+        let mut lockfile = axiom::plugin::Lockfile::from_file(package.lockfile_path())
+            .with_context(|| "failed to read lockfile")?;
+        let timeout = std::time::Duration::from_secs(self.timeout);
+        let mut stderr = std::io::stderr().lock();
+
+        for (name, source) in plugins.items() {
+            let locked = lockfile.get(name).cloned();
+
+            // Skip plugins that were already installed from the same source.
+            let up_to_date = locked.as_ref().is_some_and(|locked| {
+                locked.source() == source && plugins_dir.join(locked.file_name()).exists()
+            });
+
+            if up_to_date {
+                tracing::info!("{name} is already installed");
+                continue;
+            }
+
+            let download = axiom::plugin::resolve(source)
+                .with_context(|| format!("failed to resolve plugin '{name}'"))?;
+
+            tracing::info!("downloading {name} from {}", download.url());
+            let bytes = download
+                .fetch(timeout)
+                .with_context(|| format!("failed to download plugin '{name}'"))?;
+
+            // Write to a temporary file first so an interrupted download doesn't leave a
+            // truncated JAR behind for the server to load.
+            let path = plugins_dir.join(download.file_name());
+            let partial = path.with_extension("jar.part");
+            std::fs::write(&partial, &bytes)
+                .with_context(|| format!("failed to write plugin '{name}'"))?;
+            std::fs::rename(&partial, &path)
+                .with_context(|| format!("failed to write plugin '{name}'"))?;
+
+            // Remove the previous version so the server doesn't load both.
+            if let Some(locked) = locked.filter(|locked| locked.file_name() != download.file_name())
+            {
+                _ = std::fs::remove_file(plugins_dir.join(locked.file_name()));
+            }
+
+            lockfile.insert(
+                name.to_owned(),
+                axiom::plugin::LockedPlugin::new(source.clone(), download.file_name().to_owned()),
+            );
+            lockfile
+                .save(package.lockfile_path())
+                .with_context(|| "failed to update lockfile")?;
+
+            if !ctx.porcelain() {
+                writeln!(stderr, "📦 installed {name} ({})", download.file_name()).ok();
+            }
+        }
+
+        Ok(())
+    }
+}
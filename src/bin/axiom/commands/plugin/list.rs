@@ -0,0 +1,161 @@
+use std::io::Write;
+
+use anyhow::Context;
+use colored::Colorize;
+
+#[derive(clap::Args)]
+pub struct List;
+
+/// Describes a single row in the list of plugins.
+struct Row {
+    name: String,
+    version: String,
+    file_name: String,
+    status: Status,
+}
+
+/// Describes how an installed plugin relates to the manifest's `[plugins]` table.
+enum Status {
+    /// The plugin is installed and declared in the manifest.
+    Declared,
+    /// The plugin is installed, but not declared in the manifest.
+    Undeclared,
+    /// The plugin is declared in the manifest, but not installed.
+    Missing,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Declared => "declared".fmt(f),
+            Self::Undeclared => "undeclared".fmt(f),
+            Self::Missing => "missing".fmt(f),
+        }
+    }
+}
+
+impl crate::commands::Run for List {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let package = ctx
+            .package()
+            .with_context(|| "failed to get package manifest")?;
+
+        let declared = package
+            .manifest()
+            .plugins()
+            .map(|plugins| plugins.items().clone())
+            .unwrap_or_default();
+        let lockfile = axiom::plugin::Lockfile::from_file(package.lockfile_path())
+            .with_context(|| "failed to read lockfile")?;
+
+        // The lockfile maps the names used in the manifest to the files that were installed.
+        let declared_files = lockfile
+            .plugins()
+            .iter()
+            .filter(|(name, _)| declared.contains_key(name.as_str()))
+            .map(|(name, locked)| (locked.file_name(), name.as_str()))
+            .collect::<std::collections::HashMap<_, _>>();
+
+        let plugins_dir = package.server().plugins();
+        let mut jars = std::fs::read_dir(plugins_dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "jar"))
+            .collect::<Vec<_>>();
+        jars.sort();
+
+        let mut rows = Vec::new();
+        let mut installed = Vec::new();
+
+        for path in jars {
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+
+            // A single broken JAR shouldn't prevent the rest of the plugins from being listed.
+            let (name, version) = match axiom::plugin::PluginInfo::from_jar(&path) {
+                Ok(info) => (info.name().to_owned(), info.version().to_owned()),
+                Err(err) => {
+                    tracing::warn!("failed to read {file_name}: {err}");
+                    (file_name.clone(), "unknown".to_owned())
+                }
+            };
+
+            let status = match declared_files.get(file_name.as_str()) {
+                Some(&declared_name) => {
+                    installed.push(declared_name);
+                    Status::Declared
+                }
+                None => Status::Undeclared,
+            };
+
+            rows.push(Row {
+                name,
+                version,
+                file_name,
+                status,
+            });
+        }
+
+        for name in declared.keys() {
+            if !installed.contains(&name.as_str()) {
+                rows.push(Row {
+                    name: name.to_owned(),
+                    version: "-".to_owned(),
+                    file_name: "-".to_owned(),
+                    status: Status::Missing,
+                });
+            }
+        }
+
+        let name_width = rows
+            .iter()
+            .map(|row| row.name.len())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+        let version_width = rows
+            .iter()
+            .map(|row| row.version.len())
+            .max()
+            .unwrap_or(0)
+            .max(7);
+        let file_width = rows
+            .iter()
+            .map(|row| row.file_name.len())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        let mut stdout = ctx.output();
+        writeln!(
+            stdout,
+            "{:name_width$}  {:version_width$}  {:file_width$}  {}",
+            "NAME".bold(),
+            "VERSION".bold(),
+            "FILE".bold(),
+            "STATUS".bold()
+        )
+        .ok();
+
+        for row in rows.iter() {
+            let status = match row.status {
+                Status::Declared => row.status.to_string().green(),
+                Status::Undeclared => row.status.to_string().yellow(),
+                Status::Missing => row.status.to_string().red(),
+            };
+
+            writeln!(
+                stdout,
+                "{:name_width$}  {:version_width$}  {:file_width$}  {}",
+                row.name, row.version, row.file_name, status
+            )
+            .ok();
+        }
+
+        Ok(())
+    }
+}
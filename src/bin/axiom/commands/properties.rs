@@ -0,0 +1,26 @@
+//! This module implements the `properties` command, which compares the manifest's
+//! `[properties]` table to the server's `server.properties` file.
+
+mod diff;
+
+use crate::commands::Run;
+
+#[derive(clap::Args)]
+pub struct Properties {
+    #[command(subcommand)]
+    command: PropertiesCommand,
+}
+
+#[derive(clap::Subcommand)]
+enum PropertiesCommand {
+    /// Show how `server.properties` differs from the properties in the manifest.
+    Diff(diff::Diff),
+}
+
+impl Run for Properties {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        match &self.command {
+            PropertiesCommand::Diff(handler) => handler.run(ctx),
+        }
+    }
+}
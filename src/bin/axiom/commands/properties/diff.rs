@@ -0,0 +1,127 @@
+//! This module implements the `properties diff` command, which shows what `build` would change in
+//! `server.properties`.
+
+use std::io::Write;
+
+use anyhow::Context;
+use colored::Colorize;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Diff {
+    /// Compare against `build --overwrite`, which removes the keys that are only on disk.
+    ///
+    /// By default, keys that are only on disk are listed but don't count as differences, since
+    /// `build` keeps them.
+    #[arg(long)]
+    overwrite: bool,
+}
+
+impl crate::commands::Run for Diff {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let package = ctx
+            .package()
+            .with_context(|| "failed to get package manifest")?;
+        let path = package.server().server_properties();
+
+        // Round-trip the manifest's properties through the same format as the file on disk, so
+        // both sides are flat maps of strings (e.g., `query.port = 25565` becomes "25565").
+        let manifest = match package.manifest().properties() {
+            Some(properties) => properties
+                .to_server_properties()
+                .with_context(|| "failed to generate server.properties")?,
+            None => String::new(),
+        };
+        let manifest = axiom::manifest::Properties::from_server_properties(&manifest);
+
+        let on_disk = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                tracing::warn!("{} does not exist yet", path.display());
+                String::new()
+            }
+            Err(err) => {
+                return Err(anyhow::Error::new(err)
+                    .context(format!("failed to read {}", path.display()))
+                    .into());
+            }
+        };
+        let on_disk = axiom::manifest::Properties::from_server_properties(&on_disk);
+
+        let (manifest, on_disk) = (manifest.items(), on_disk.items());
+        let mut stdout = ctx.output();
+        let mut differences = 0;
+
+        let added = manifest
+            .iter()
+            .filter(|(key, _)| !on_disk.contains_key(*key))
+            .collect::<Vec<_>>();
+
+        if !added.is_empty() {
+            writeln!(stdout, "{}", "Only in the manifest (will be added):".bold()).ok();
+
+            for (key, value) in added.iter() {
+                let line = format!("+ {key}={}", value.as_str().unwrap_or_default());
+                writeln!(stdout, "  {}", line.green()).ok();
+            }
+
+            differences += added.len();
+        }
+
+        let changed = manifest
+            .iter()
+            .filter_map(|(key, value)| Some((key, on_disk.get(key)?, value)))
+            .filter(|(_, old, new)| old != new)
+            .collect::<Vec<_>>();
+
+        if !changed.is_empty() {
+            writeln!(stdout, "{}", "Different values (will be changed):".bold()).ok();
+
+            for (key, old, new) in changed.iter() {
+                let old = old.as_str().unwrap_or_default();
+                let new = new.as_str().unwrap_or_default();
+                let line = format!("~ {key}: {old} -> {new}");
+                writeln!(stdout, "  {}", line.yellow()).ok();
+            }
+
+            differences += changed.len();
+        }
+
+        let removed = on_disk
+            .iter()
+            .filter(|(key, _)| !manifest.contains_key(*key))
+            .collect::<Vec<_>>();
+
+        if !removed.is_empty() {
+            let heading = match self.overwrite {
+                true => "Only on disk (will be removed):",
+                false => "Only on disk (lost with `build --overwrite`):",
+            };
+            writeln!(stdout, "{}", heading.bold()).ok();
+
+            for (key, value) in removed.iter() {
+                let line = format!("- {key}={}", value.as_str().unwrap_or_default());
+                writeln!(stdout, "  {}", line.red()).ok();
+            }
+
+            if self.overwrite {
+                differences += removed.len();
+            }
+        }
+
+        if differences > 0 {
+            return Err(crate::error::Error::new_with_hint(
+                "run `axiom build` to apply the properties from the manifest",
+                anyhow::anyhow!("found {differences} difference(s) in server.properties"),
+            ));
+        }
+
+        if !ctx.porcelain() {
+            writeln!(
+                std::io::stderr(),
+                "🟢 server.properties matches the manifest"
+            )
+            .ok();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+//! This module implements the `prune` command, which removes files that pile up inside of the
+//! package over time.
+
+mod logs;
+
+use crate::commands::Run;
+
+#[derive(clap::Args)]
+pub struct Prune {
+    #[command(subcommand)]
+    command: PruneCommand,
+}
+
+#[derive(clap::Subcommand)]
+enum PruneCommand {
+    /// Remove old rotated logs from the server's `logs` directory, always keeping `latest.log`.
+    Logs(logs::Logs),
+}
+
+impl Run for Prune {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        match &self.command {
+            PruneCommand::Logs(handler) => handler.run(ctx),
+        }
+    }
+}
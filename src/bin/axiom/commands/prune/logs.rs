@@ -0,0 +1,204 @@
+//! This module implements the `prune logs` command, which removes the compressed logs the server
+//! rotates `latest.log` into.
+
+use std::io::Write;
+
+use anyhow::Context;
+use colored::Colorize;
+
+/// The extension the server gives to rotated logs (e.g., `2025-06-28-1.log.gz`).
+const ROTATED_LOG_EXTENSION: &str = ".log.gz";
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Logs {
+    /// Also remove rotated logs that were last written to more than this many days ago, even if
+    /// they are among the ones kept by `--keep`.
+    #[arg(long, value_name = "DAYS")]
+    older_than: Option<u64>,
+
+    /// How many of the most recent rotated logs to keep.
+    #[arg(long, value_name = "N", default_value = "30")]
+    keep: usize,
+
+    /// List the logs that would be removed, without removing them.
+    #[arg(long, short = 'n')]
+    dry_run: bool,
+}
+
+/// Describes one of the rotated logs in the server's `logs` directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RotatedLog {
+    path: std::path::PathBuf,
+    modified: std::time::SystemTime,
+    size: u64,
+}
+
+impl crate::commands::Run for Logs {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let package = ctx
+            .package()
+            .with_context(|| "failed to get package manifest")?;
+        let directory = package.server().logs();
+
+        let logs = rotated_logs(directory)
+            .with_context(|| format!("failed to read {}", directory.display()))?;
+        let mut stderr = std::io::stderr().lock();
+
+        if logs.is_empty() {
+            writeln!(
+                stderr,
+                "{}",
+                format!("no rotated logs found in {}", directory.display()).bold()
+            )
+            .ok();
+            return Ok(());
+        }
+
+        let older_than = self
+            .older_than
+            // Absurdly large values just mean "never", rather than overflowing.
+            .map(|days| std::time::Duration::from_secs(days.saturating_mul(24 * 60 * 60)));
+        let expired = expired_logs(&logs, self.keep, older_than, std::time::SystemTime::now());
+
+        let mut reclaimed = 0;
+
+        for log in &expired {
+            if self.dry_run {
+                writeln!(stderr, "would remove {}", log.path.display()).ok();
+            } else {
+                std::fs::remove_file(&log.path)
+                    .with_context(|| format!("failed to remove {}", log.path.display()))?;
+                tracing::debug!("removed {}", log.path.display());
+            }
+
+            reclaimed += log.size;
+        }
+
+        let reclaimed = crate::commands::cache::format_size(reclaimed);
+        let summary = match (self.dry_run, expired.len()) {
+            (_, 0) => format!("all {} rotated log(s) are being kept", logs.len()),
+            (true, count) => {
+                format!("{count} log file(s) would be removed, reclaiming {reclaimed}")
+            }
+            (false, count) => format!("removed {count} log file(s), reclaiming {reclaimed}"),
+        };
+        writeln!(stderr, "{}", summary.bold()).ok();
+
+        Ok(())
+    }
+}
+
+/// Get the rotated logs in `directory`, newest first.
+///
+/// A missing directory has no logs, since the server only creates it the first time it runs.
+fn rotated_logs(directory: &std::path::Path) -> Result<Vec<RotatedLog>, std::io::Error> {
+    let entries = match std::fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut logs = Vec::new();
+
+    for entry in entries {
+        let entry = entry?;
+        let is_rotated_log = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.ends_with(ROTATED_LOG_EXTENSION));
+        let metadata = entry.metadata()?;
+
+        if !is_rotated_log || !metadata.is_file() {
+            continue;
+        }
+
+        logs.push(RotatedLog {
+            path: entry.path(),
+            modified: metadata.modified()?,
+            size: metadata.len(),
+        });
+    }
+
+    // Sort by name too, since logs rotated within the same second have the same timestamp.
+    logs.sort_by(|a, b| b.modified.cmp(&a.modified).then(b.path.cmp(&a.path)));
+
+    Ok(logs)
+}
+
+/// Get the logs that are not among the `keep` newest, plus the ones that were last modified more
+/// than `older_than` before `now`.
+///
+/// `logs` must be sorted newest first (see [`rotated_logs`]).
+fn expired_logs(
+    logs: &[RotatedLog],
+    keep: usize,
+    older_than: Option<std::time::Duration>,
+    now: std::time::SystemTime,
+) -> Vec<&RotatedLog> {
+    logs.iter()
+        .enumerate()
+        .filter(|(i, log)| {
+            let too_old = older_than.is_some_and(|older_than| {
+                now.duration_since(log.modified)
+                    .is_ok_and(|age| age > older_than)
+            });
+            *i >= keep || too_old
+        })
+        .map(|(_, log)| log)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotated_logs() {
+        let root = tempfile::tempdir().unwrap();
+        let directory = root.path().join("logs");
+        assert!(rotated_logs(&directory).unwrap().is_empty());
+
+        std::fs::create_dir_all(&directory).unwrap();
+        for name in ["latest.log", "2025-06-27-1.log.gz", "2025-06-28-1.log.gz"] {
+            std::fs::write(directory.join(name), "").unwrap();
+        }
+
+        let mut names = rotated_logs(&directory)
+            .unwrap()
+            .into_iter()
+            .map(|log| log.path.file_name().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["2025-06-27-1.log.gz", "2025-06-28-1.log.gz"]);
+    }
+
+    #[test]
+    fn test_expired_logs() {
+        const DAY: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+        let now = std::time::SystemTime::UNIX_EPOCH + 100 * DAY;
+        let logs = (1..=4)
+            .map(|days| RotatedLog {
+                path: format!("{days}.log.gz").into(),
+                modified: now - days * DAY,
+                size: 0,
+            })
+            .collect::<Vec<_>>();
+
+        let names = |keep, older_than| {
+            expired_logs(&logs, keep, older_than, now)
+                .iter()
+                .map(|log| log.path.to_str().unwrap().to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(2, None), ["3.log.gz", "4.log.gz"]);
+        assert!(names(30, None).is_empty());
+        assert_eq!(names(30, Some(2 * DAY)), ["3.log.gz", "4.log.gz"]);
+        assert_eq!(
+            names(1, Some(2 * DAY)),
+            ["2.log.gz", "3.log.gz", "4.log.gz"]
+        );
+        assert_eq!(names(0, None).len(), 4);
+    }
+}
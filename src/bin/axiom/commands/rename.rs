@@ -0,0 +1,128 @@
+//! This module implements the `rename` command, which changes the name of a package.
+
+use std::io::Write;
+
+use anyhow::Context;
+use colored::Colorize;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Rename {
+    /// The new name of the package.
+    pub(crate) name: String,
+
+    /// Also rename the package's directory to the new name.
+    #[arg(long)]
+    pub(crate) directory: bool,
+}
+
+impl crate::commands::Run for Rename {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        axiom::manifest::Package::validate_name(&self.name)
+            .map_err(|err| crate::error::Error::new(err).with_hint(|| "choose a different name"))?;
+
+        let package = ctx
+            .package()
+            .with_context(|| "failed to get package manifest")?;
+        let old_name = package.name().to_owned();
+
+        if old_name == self.name {
+            crate::bail!("the package is already named '{}'", self.name);
+        }
+
+        // The server runs in a tmux window named after the package, which would be lost track of.
+        if ctx.backend()?.is_running(&package)? {
+            return Err(crate::error::Error::new_with_hint(
+                "stop the server with `axiom stop` first",
+                anyhow::anyhow!("cannot rename the package while its server is running"),
+            ));
+        }
+
+        let new_path = match self.directory {
+            true => Some(
+                package
+                    .path()
+                    .parent()
+                    .with_context(|| "failed to get the package's parent directory")?
+                    .join(&self.name),
+            ),
+            false => None,
+        };
+
+        if let Some(new_path) = new_path.as_ref().filter(|path| path.exists()) {
+            crate::bail!("{} already exists", new_path.display());
+        }
+
+        if let Some(existing) = find_package_named(package.path(), &self.name) {
+            return Err(crate::error::Error::new_with_hint(
+                "package names must be unique, so choose a different name",
+                anyhow::anyhow!(
+                    "a package named '{}' already exists at {}",
+                    self.name,
+                    existing.display()
+                ),
+            ));
+        }
+
+        // Edit the raw manifest to preserve the user's comments and formatting.
+        let manifest_content = std::fs::read_to_string(package.manifest_path())
+            .with_context(|| "failed to read manifest")?;
+        let mut document = manifest_content
+            .parse::<toml_edit::DocumentMut>()
+            .with_context(|| "failed to parse manifest")?;
+        document["package"]["name"] = toml_edit::value(&self.name);
+        std::fs::write(package.manifest_path(), document.to_string())
+            .with_context(|| "failed to set the new name in the manifest")?;
+
+        if let Some(new_path) = &new_path {
+            std::fs::rename(package.path(), new_path).with_context(|| {
+                format!(
+                    "failed to move {} to {}",
+                    package.path().display(),
+                    new_path.display()
+                )
+            })?;
+
+            if let Err(err) = crate::registry::remove(package.path())
+                .and_then(|()| crate::registry::add(new_path))
+            {
+                tracing::warn!("failed to update the package registry: {err:#}");
+            }
+        }
+
+        let mut stdout = ctx.output();
+        writeln!(stdout, "{}: {}", "Old Name".bold(), old_name).ok();
+        writeln!(stdout, "{}: {}", "New Name".bold(), self.name).ok();
+
+        if let Some(new_path) = &new_path {
+            writeln!(stdout, "{}: {}", "Path".bold(), new_path.display()).ok();
+        }
+
+        Ok(())
+    }
+}
+
+/// Find another package called `name`, among the siblings of `package_path` and the registered
+/// packages.
+pub(crate) fn find_package_named(
+    package_path: &std::path::Path,
+    name: &str,
+) -> Option<std::path::PathBuf> {
+    let siblings = package_path
+        .parent()
+        .map(crate::commands::list::find_packages)
+        .unwrap_or_default();
+    let registered = crate::registry::all().unwrap_or_else(|err| {
+        tracing::warn!("failed to read the package registry: {err:#}");
+        Vec::new()
+    });
+    let package_path = std::path::absolute(package_path).ok()?;
+
+    siblings
+        .into_iter()
+        .chain(registered)
+        .filter(|path| std::path::absolute(path).ok().as_ref() != Some(&package_path))
+        .find(|path| {
+            axiom::Manifest::from_directory(path)
+                .is_ok_and(|manifest| manifest.package().name() == name)
+        })
+}
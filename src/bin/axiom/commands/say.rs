@@ -0,0 +1,99 @@
+//! This module implements the `say` command, which broadcasts a message to everyone on the
+//! package's running server.
+
+use std::io::Read;
+
+use anyhow::Context;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Say {
+    /// The message to broadcast. If omitted, the message is read from stdin.
+    ///
+    /// Each line of the message is sent as a separate announcement.
+    pub(crate) message: Option<String>,
+}
+
+impl crate::commands::Run for Say {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let package = ctx
+            .package()
+            .with_context(|| "failed to get package manifest")?;
+        let backend = package
+            .manifest()
+            .launcher()
+            .map(|launcher| *launcher.backend())
+            .unwrap_or_default();
+
+        // Messages are typed into the server console, which only the tmux backend has access to.
+        if !matches!(backend, axiom::manifest::Backend::Tmux) {
+            return Err(crate::error::Error::new_with_hint(
+                "set `backend = \"tmux\"` in the `[launcher]` section of Axiom.toml",
+                anyhow::anyhow!("`axiom say` requires the tmux backend"),
+            ));
+        }
+
+        let message = match &self.message {
+            Some(message) => message.to_owned(),
+            None => {
+                let mut message = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut message)
+                    .with_context(|| "failed to read message from stdin")?;
+                message
+            }
+        };
+
+        let lines = announcements(&message);
+
+        if lines.is_empty() {
+            return Err(crate::error::Error::new_with_hint(
+                "pass the message as an argument, or pipe it into `axiom say`",
+                anyhow::anyhow!("the message is empty"),
+            ));
+        }
+
+        let backend = ctx.backend()?;
+
+        if !backend.is_running(&package)? {
+            return Err(crate::error::Error::new_with_hint(
+                "start the server with `axiom start` first",
+                anyhow::anyhow!("the server is not running"),
+            ));
+        }
+
+        for line in lines {
+            backend.send_command(&package, &format!("say {line}"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Split `message` into the lines to announce, skipping blank lines.
+///
+/// The console runs a command as soon as it sees a newline, so each line needs its own `say`.
+fn announcements(message: &str) -> Vec<&str> {
+    message
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_announcements() {
+        assert_eq!(
+            announcements("Restarting in 5 minutes"),
+            vec!["Restarting in 5 minutes"]
+        );
+        assert_eq!(
+            announcements("\nRestarting soon\n\n  Save your work!  \n"),
+            vec!["Restarting soon", "Save your work!"]
+        );
+        assert!(announcements(" \n\n").is_empty());
+    }
+}
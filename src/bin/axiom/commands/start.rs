@@ -1,109 +1,105 @@
 use std::io::{BufRead, Read, Seek, Write};
-use std::os::unix::process::ExitStatusExt;
 
 use anyhow::Context;
+use colored::Colorize;
 
 use super::build::Build;
-use super::{TMUX_SERVER_NAME, TMUX_SESSION_NAME};
+
+/// The port Minecraft servers listen on when `server-port` is not set.
+const DEFAULT_SERVER_PORT: u16 = 25565;
+
+/// How many seconds to wait for the server to come online when `--wait-timeout` is not set.
+const DEFAULT_WAIT_TIMEOUT: u64 = 60;
+
+/// How many seconds to wait for `latest.log` when `startup_grace_secs` is not set.
+const DEFAULT_STARTUP_GRACE: u64 = 5;
+
+/// How many seconds to wait between checks of `latest.log` when `poll_interval_secs` is not set.
+const DEFAULT_POLL_INTERVAL: u64 = 5;
 
 #[derive(clap::Args)]
-pub struct Start;
+pub struct Start {
+    /// Start the server even if another program is already listening on its port.
+    #[arg(long)]
+    pub(crate) force: bool,
+
+    /// Return as soon as the server has been launched, without waiting for it to come online.
+    ///
+    /// This means Axiom doesn't check whether the server actually started successfully; use
+    /// `axiom status` or read the logs to find out.
+    #[arg(long, short = 'd', conflicts_with = "wait_timeout")]
+    pub(crate) detach: bool,
+
+    /// How many seconds to wait for the server to come online before giving up.
+    ///
+    /// Increase this for servers that take a long time to start (e.g., while generating a new
+    /// world).
+    #[arg(long, value_name = "SECONDS", default_value_t = DEFAULT_WAIT_TIMEOUT)]
+    pub(crate) wait_timeout: u64,
+}
 
 impl crate::commands::Run for Start {
     fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
         let package = ctx
             .package()
             .with_context(|| "failed to get package manifest")?;
+        let backend = ctx.backend()?;
 
-        let tmux_window_name = package.name();
-
-        let status = std::process::Command::new("tmux")
-            .current_dir(package.path())
-            .args([
-                "-L",
-                TMUX_SERVER_NAME,
-                "has-session",
-                "-t",
-                &format!("={}:{}", TMUX_SESSION_NAME, tmux_window_name),
-            ])
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .status()
-            .with_context(|| "failed to execute command 'tmux'")?;
-
-        if status.success() {
+        if backend.is_running(&package)? {
             crate::bail!("a package with the same name is already running");
         }
 
+        if !self.force {
+            ensure_port_available(ctx, &package)?;
+        }
+
         tracing::info!("building the Minecraft server");
-        Build::run(&Build { accept_eula: false }, ctx)?;
+        Build::run(
+            &Build {
+                accept_eula: false,
+                check: false,
+                overwrite: false,
+                init_timeout: super::build::DEFAULT_INIT_TIMEOUT,
+            },
+            ctx,
+        )?;
 
         let server = package.server();
+        // Without a server JAR, `start.sh` fails inside of the backend where nobody sees it, and
+        // all we would notice is that `latest.log` never shows up.
+        ensure_server_jar(server)?;
 
         tracing::info!("starting the server");
-        let status = std::process::Command::new("tmux")
-            .args([
-                "-L",
-                TMUX_SERVER_NAME,
-                "new-window",
-                "-c",
-                server
-                    .path()
-                    .to_str()
-                    .with_context(|| "failed to convert current directory to string")?,
-                "-d",
-                "-t",
-                &format!("={}", TMUX_SESSION_NAME),
-                "-n",
-                tmux_window_name,
-                "./start.sh",
-            ])
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .status()
-            .with_context(|| "failed to execute tmux command")?;
-
-        if !status.success() {
-            let status = std::process::Command::new("tmux")
-                .args([
-                    "-L",
-                    TMUX_SERVER_NAME,
-                    "new-session",
-                    "-c",
-                    server
-                        .path()
-                        .to_str()
-                        .with_context(|| "failed to convert current directory to string")?,
-                    "-d",
-                    "-s",
-                    TMUX_SESSION_NAME,
-                    "-n",
-                    tmux_window_name,
-                    "./start.sh",
-                ])
-                .stdout(std::process::Stdio::null())
-                .stderr(std::process::Stdio::null())
-                .status()
-                .with_context(|| "failed to execute tmux command")?;
-
-            if !status.success() {
-                match status.code() {
-                    Some(code) => tracing::error!("command terminated with exit code: {code}"),
-                    None => tracing::error!(
-                        "command terminated via signal: {}",
-                        status.signal().unwrap()
-                    ),
-                }
+        backend.start(&package)?;
+        let deadline =
+            std::time::Instant::now() + std::time::Duration::from_secs(self.wait_timeout);
 
-                crate::bail!("failed to create tmux session");
+        if self.detach {
+            if !backend.is_running(&package)? {
+                crate::bail!("the server exited immediately after being launched");
             }
+
+            if !ctx.porcelain() {
+                writeln!(std::io::stderr(), "🟡 server is starting in the background").ok();
+            }
+            return Ok(());
         }
 
         let latest_log = server.logs().join("latest.log");
 
-        let sleep_duration = std::time::Duration::from_secs(5);
+        let launcher = package.manifest().launcher();
+        let sleep_duration = std::time::Duration::from_secs(
+            launcher
+                .and_then(|launcher| launcher.startup_grace_secs())
+                .unwrap_or(DEFAULT_STARTUP_GRACE),
+        );
+        let poll_interval = std::time::Duration::from_secs(
+            launcher
+                .and_then(|launcher| launcher.poll_interval_secs())
+                .unwrap_or(DEFAULT_POLL_INTERVAL),
+        );
         tracing::debug!(
-            "sleeping for {:?} seconds to give the server a chance to create a new latest.log...",
+            "sleeping for {:?} to give the server a chance to create a new latest.log...",
             sleep_duration
         );
         std::thread::sleep(sleep_duration);
@@ -119,7 +115,7 @@ impl crate::commands::Run for Start {
         );
 
         let mut stderr = std::io::stderr().lock();
-        for attempt in 0..12 {
+        for attempt in 0.. {
             tracing::debug!("Checking server status: attempt #{}", attempt + 1);
 
             reader
@@ -135,25 +131,19 @@ impl crate::commands::Run for Start {
             for line in lines {
                 tracing::debug!("Reading line: {}", line);
 
-                if line.ends_with(r#"s)! For help, type "help""#) {
-                    // TODO: Provide better output:
-                    //
-                    // Built {package.name()} in XX.XXs
-                    // Starting the Minecraft server...
-                    // ----------------------------------------------------------------------------
-                    // Version: 1.21.6 (#44)
-                    // Server IP: localhost
-                    // Port: 25565
-                    // ----------------------------------------------------------------------------
-                    // Server is now online!
-                    //
-                    // Use `axiom --help` for a list of available commands.
-                    writeln!(stderr, "🟢 server is now online!").ok();
+                if startup_time(&line).is_some() {
+                    ctx.emit(crate::event::Event::Online);
+
+                    if !ctx.porcelain() {
+                        let summary = StartupSummary::new(&package, &line)?;
+                        summary.print(&mut stderr, package.name());
+                        writeln!(stderr, "🟢 server is now online!").ok();
+                    }
                     return Ok(());
                 } else if line.ends_with("Failed to start the minecraft server") {
                     let message = "An error occurred while starting the server".to_owned();
                     let err = anyhow::anyhow!(message);
-                    return Err(crate::error::Error::new_with_hint(err, hint));
+                    return Err(crate::error::Error::new_with_hint(hint, err));
                 } else {
                     position = reader
                         .stream_position()
@@ -161,7 +151,11 @@ impl crate::commands::Run for Start {
                 }
             }
 
-            std::thread::sleep(std::time::Duration::from_secs(5));
+            if std::time::Instant::now() >= deadline {
+                break;
+            }
+
+            std::thread::sleep(poll_interval);
         }
 
         // Check if the window is still open as a last effort.
@@ -169,8 +163,280 @@ impl crate::commands::Run for Start {
 
         let message = "Axiom timed out while waiting for the server to start".to_owned();
         Err(crate::error::Error::new_with_hint(
+            format!("{hint}, or use `--wait-timeout` to wait longer"),
             anyhow::anyhow!(message),
-            hint,
         ))
     }
 }
+
+/// The details shown once the server is online.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StartupSummary {
+    /// How long the server took to start, according to its log.
+    startup_time: Option<std::time::Duration>,
+    /// The Minecraft version the server is running.
+    version: String,
+    /// The PaperMC build the server is running.
+    build: i64,
+    /// The address players can connect to.
+    server_ip: String,
+    /// The port players can connect to.
+    port: u16,
+}
+
+impl StartupSummary {
+    /// Gather the details of the package's server, which logged `done_line` once it started.
+    fn new(package: &axiom::Package, done_line: &str) -> Result<Self, anyhow::Error> {
+        let manifest = package.manifest();
+
+        // Reading the version from the name of the linked JAR is instant, unlike asking the JAR.
+        let (version, build) = match package.server().linked_build_info() {
+            Some(build_info) => (build_info.version().to_owned(), build_info.build()),
+            None => (
+                manifest.server().version().to_owned(),
+                manifest.server().build(),
+            ),
+        };
+
+        // An empty `server-ip` means the server listens on every address, including localhost.
+        let server_ip = manifest
+            .properties()
+            .and_then(|properties| {
+                properties
+                    .items()
+                    .get("server-ip")
+                    .and_then(|value| value.as_str())
+            })
+            .filter(|address| !address.is_empty())
+            .unwrap_or("localhost")
+            .to_owned();
+
+        Ok(Self {
+            startup_time: startup_time(done_line),
+            version,
+            build,
+            server_ip,
+            port: server_port(manifest)?,
+        })
+    }
+
+    /// Print the summary, for the package named `name`.
+    fn print(&self, f: &mut impl Write, name: &str) {
+        if let Some(startup_time) = self.startup_time {
+            writeln!(f, "Started {name} in {:.2}s", startup_time.as_secs_f64()).ok();
+        }
+
+        let separator = "-".repeat(78);
+        writeln!(f, "{separator}").ok();
+        writeln!(
+            f,
+            "{}: {} (#{})",
+            "Version".bold(),
+            self.version,
+            self.build
+        )
+        .ok();
+        writeln!(f, "{}: {}", "Server IP".bold(), self.server_ip).ok();
+        writeln!(f, "{}: {}", "Port".bold(), self.port).ok();
+        writeln!(f, "{separator}").ok();
+    }
+}
+
+/// Get how long the server took to start from the line it logs once it is done, which looks
+/// like: `[12:34:56] [Server thread/INFO]: Done (12.345s)! For help, type "help"`.
+///
+/// Returns `None` if `line` is not that line. Only the `(12.345s)!` part is matched, since the
+/// words around it may be translated or changed by plugins.
+fn startup_time(line: &str) -> Option<std::time::Duration> {
+    line.match_indices('(').find_map(|(index, _)| {
+        let rest = &line[index + 1..];
+        let (seconds, _) = rest.split_once("s)!")?;
+
+        // Some locales use a comma as the decimal separator.
+        if seconds.is_empty()
+            || !seconds
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == '.' || c == ',')
+        {
+            return None;
+        }
+
+        seconds
+            .replace(',', ".")
+            .parse::<f64>()
+            .ok()
+            .and_then(|seconds| std::time::Duration::try_from_secs_f64(seconds).ok())
+    })
+}
+
+/// Get the port the server will listen on, based on the `server-port` property.
+fn server_port(manifest: &axiom::Manifest) -> Result<u16, anyhow::Error> {
+    let port = manifest.properties().and_then(|properties| {
+        properties
+            .items()
+            .get("server-port")
+            .and_then(|value| value.as_integer())
+    });
+
+    match port {
+        Some(port) => u16::try_from(port).with_context(|| format!("invalid port number {port}")),
+        None => Ok(DEFAULT_SERVER_PORT),
+    }
+}
+
+/// Ensure the server has a `server.jar` to run, following it to the cache if it is a link.
+fn ensure_server_jar(server: &axiom::package::Server) -> Result<(), crate::error::Error> {
+    let server_jar = server.server_jar();
+
+    if server_jar.is_file() {
+        return Ok(());
+    }
+
+    let message = match std::fs::read_link(server_jar) {
+        Ok(target) => format!(
+            "server.jar links to {}, which does not exist",
+            target.display()
+        ),
+        Err(_) => format!("no server.jar in {}", server.path().display()),
+    };
+
+    Err(crate::error::Error::new_with_hint(
+        "run `axiom build` first to download the server JAR",
+        anyhow::anyhow!(message),
+    ))
+}
+
+/// Ensure nothing else is listening on the port the package's server is configured to use.
+///
+/// Without this check, a server whose port is already taken fails to bind without much notice,
+/// which is easy to miss unless you read the logs.
+fn ensure_port_available(
+    ctx: &mut crate::context::Context,
+    package: &axiom::Package,
+) -> Result<(), crate::error::Error> {
+    let port = server_port(package.manifest())?;
+    let address = package
+        .manifest()
+        .properties()
+        .and_then(|properties| {
+            properties
+                .items()
+                .get("server-ip")
+                .and_then(|value| value.as_str())
+        })
+        .filter(|address| !address.is_empty())
+        .unwrap_or("0.0.0.0");
+
+    let err = match std::net::TcpListener::bind((address, port)) {
+        Ok(_) => return Ok(()),
+        Err(err) => err,
+    };
+
+    if err.kind() != std::io::ErrorKind::AddrInUse {
+        tracing::warn!("failed to check whether port {port} is available: {err}");
+        return Ok(());
+    }
+
+    // Try to find which of our own servers is using the port. Servers that aren't running in
+    // tmux (or aren't managed by Axiom at all) can't be identified.
+    let owner = super::list::running_servers(&ctx.tmux_session())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, path)| path != package.path())
+        .find(|(_, path)| {
+            axiom::Manifest::from_directory(path)
+                .ok()
+                .and_then(|manifest| server_port(&manifest).ok())
+                .is_some_and(|other| other == port)
+        })
+        .map(|(name, _)| name);
+
+    let message = match owner {
+        Some(name) => format!("port {port} is already in use by package '{name}'"),
+        None => format!("port {port} is already in use"),
+    };
+
+    Err(crate::error::Error::new_with_hint(
+        "change `server-port` in the [properties] section of the manifest, \
+        or use `--force` to start the server anyway",
+        anyhow::anyhow!(message),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DONE_LINE: &str =
+        r#"[12:34:56] [Server thread/INFO]: Done (12.345s)! For help, type "help""#;
+
+    #[test]
+    fn test_startup_time() {
+        assert_eq!(
+            startup_time(DONE_LINE),
+            Some(std::time::Duration::from_millis(12345))
+        );
+        assert_eq!(startup_time("[12:34:56] [Server thread/INFO]: Done"), None);
+
+        // The rest of the line may be translated.
+        assert_eq!(
+            startup_time(
+                r#"[12:34:56] [Server thread/INFO]: Fertig (3,5s)! Für Hilfe, gib "help" ein"#
+            ),
+            Some(std::time::Duration::from_millis(3500))
+        );
+        assert_eq!(
+            startup_time("[12:34:56] [Server thread/INFO]: 完成 (7.250s)!"),
+            Some(std::time::Duration::from_millis(7250))
+        );
+        assert_eq!(
+            startup_time("[12:34:56] [Server thread/INFO]: Preparing spawn area (took 5s)!"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ensure_server_jar() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path();
+        let package = crate::testing::package(path, "");
+        let server = package.server();
+        std::fs::create_dir_all(server.path()).unwrap();
+
+        let missing = ensure_server_jar(server).map_err(|err| err.to_string());
+
+        let cached_jar = path.join("paper-1.21.6-34.jar");
+        symlink::symlink_file(&cached_jar, server.server_jar()).unwrap();
+        let dangling = ensure_server_jar(server).map_err(|err| err.to_string());
+
+        std::fs::write(&cached_jar, "").unwrap();
+        let linked = ensure_server_jar(server).map_err(|err| err.to_string());
+
+        assert!(missing.unwrap_err().starts_with("no server.jar in "));
+        assert!(
+            dangling
+                .unwrap_err()
+                .ends_with("paper-1.21.6-34.jar, which does not exist")
+        );
+        assert_eq!(linked, Ok(()));
+    }
+
+    #[test]
+    fn test_startup_summary() {
+        // The package doesn't need to exist, since the summary is read from the manifest.
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("missing");
+        let package = crate::testing::package(&path, "\n[properties]\nserver-port = 25570\n");
+
+        assert_eq!(
+            StartupSummary::new(&package, DONE_LINE).unwrap(),
+            StartupSummary {
+                startup_time: Some(std::time::Duration::from_millis(12345)),
+                version: "1.21.6".to_owned(),
+                build: 34,
+                server_ip: "localhost".to_owned(),
+                port: 25570,
+            }
+        );
+    }
+}
@@ -1,8 +1,13 @@
 use std::io::{BufRead, Read, Seek, Write};
 
 use anyhow::Context;
+use colored::Colorize;
 
-use super::{TMUX_SERVER_NAME, TMUX_SESSION_NAME};
+/// How many seconds to wait for the server to stop before giving up.
+const STOP_TIMEOUT: u64 = 36;
+
+/// How many seconds to wait between checks of `latest.log` when `poll_interval_secs` is not set.
+const DEFAULT_POLL_INTERVAL: u64 = 3;
 
 #[derive(Debug, Clone, clap::Args)]
 pub struct Stop {}
@@ -24,37 +29,15 @@ impl crate::commands::Run for Stop {
             .seek(std::io::SeekFrom::End(0))
             .with_context(|| "failed to seek to end of file")?;
 
-        // Send CTRL+C into the target server's pane.
-        //
-        // There were 2 alternatives I also considered:
-        // - Send "stop" and "Enter"
-        // - Send SIGTERM to the process directly.
-        //
-        // Sending "stop" assumes that there is no other command currently being typed into the console.
-        // If there is a command being typed, we have to clear it (or give up and return an error,
-        // as they could be actively typing while we are trying to close).
-        //
-        // I think ideally we would send SIGTERM to the process directly, but we would need a reliable
-        // way to get the process ID for the pane.
-        //
-        // I think sending CTRL+C is the fastest and simplest solution we can implement right now.
-        let status = std::process::Command::new("tmux")
-            .args([
-                "-L",
-                TMUX_SERVER_NAME,
-                "send-keys",
-                "-t",
-                &format!("={}:{}", TMUX_SESSION_NAME, package.name()),
-                "C-c",
-            ])
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .status()
-            .with_context(|| "failed to execute command 'tmux'")?;
-
-        if !status.success() {
-            crate::bail!("failed to send Ctrl+C (SIGTERM) to tmux window");
-        }
+        ctx.backend()?.stop(&package)?;
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(STOP_TIMEOUT);
+        let poll_interval = std::time::Duration::from_secs(
+            package
+                .manifest()
+                .launcher()
+                .and_then(|launcher| launcher.poll_interval_secs())
+                .unwrap_or(DEFAULT_POLL_INTERVAL),
+        );
 
         // TODO: Maybe it would be better to have a command that pipes the output of
         // the `latest.log` file into `less` and suggest running that command instead?
@@ -64,7 +47,7 @@ impl crate::commands::Run for Stop {
         );
 
         let mut stderr = std::io::stderr().lock();
-        for attempt in 0..12 {
+        for attempt in 0.. {
             tracing::debug!("Checking server status: attempt #{}", attempt + 1);
 
             reader
@@ -81,16 +64,16 @@ impl crate::commands::Run for Stop {
                 tracing::debug!("Reading line: {}", line);
 
                 if line.ends_with(r#"Stopping server"#) {
-                    // TODO: Provide better output:
-                    //
-                    // Stopping the Minecraft server...
-                    // ----------------------------------------------------------------------------
-                    // Uptime: 2h 15m
-                    // Players joined: 7
-                    // Most concurrent players: 2
-                    // ----------------------------------------------------------------------------
-                    // Server has been stopped.
-                    writeln!(stderr, "🔴 server has been stopped").ok();
+                    ctx.emit(crate::event::Event::Stopped);
+
+                    if !ctx.porcelain() {
+                        // The stats cover the whole session, not just what was logged since `stop`.
+                        let stats = std::fs::read_to_string(&latest_log)
+                            .map(|log| session_stats(log.lines()))
+                            .unwrap_or_default();
+                        print_session_stats(&mut stderr, &stats);
+                        writeln!(stderr, "🔴 server has been stopped").ok();
+                    }
                     return Ok(());
                 } else {
                     position = reader
@@ -99,14 +82,162 @@ impl crate::commands::Run for Stop {
                 }
             }
 
-            std::thread::sleep(std::time::Duration::from_secs(3));
+            if std::time::Instant::now() >= deadline {
+                break;
+            }
+
+            std::thread::sleep(poll_interval);
         }
 
         // Failed to stop the server / determine if it is stopped.
         let message = "Axiom timed out while waiting for the server to stop".to_owned();
         Err(crate::error::Error::new_with_hint(
-            anyhow::anyhow!(message),
             hint,
+            anyhow::anyhow!(message),
         ))
     }
 }
+
+/// What happened while the server was running, according to its `latest.log`.
+///
+/// Each field is `None` if the log doesn't have enough information to work it out (e.g., the
+/// beginning of the log is missing).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct SessionStats {
+    /// How long the server ran for, from starting up to shutting down.
+    uptime: Option<std::time::Duration>,
+    /// How many different players joined the server.
+    players_joined: Option<usize>,
+    /// The most players that were online at the same time.
+    peak_players: Option<usize>,
+}
+
+/// Work out the [`SessionStats`] from the lines of a server's `latest.log`.
+fn session_stats<'a>(lines: impl IntoIterator<Item = &'a str>) -> SessionStats {
+    let mut started_at = None;
+    let mut stopped_at = None;
+    let mut joined = std::collections::HashSet::new();
+    let mut online = std::collections::HashSet::new();
+    let mut peak = 0;
+
+    for line in lines {
+        // Each line looks like: `[12:34:56] [Server thread/INFO]: Steve joined the game`.
+        let Some((_, message)) = line.split_once("]: ") else {
+            continue;
+        };
+
+        if message.starts_with("Starting minecraft server version") {
+            started_at = log_time(line);
+        } else if message == "Stopping server" {
+            stopped_at = log_time(line);
+        } else if let Some(player) = message.strip_suffix(" joined the game") {
+            if is_player_name(player) {
+                joined.insert(player);
+                online.insert(player);
+                peak = peak.max(online.len());
+            }
+        } else if let Some(player) = message.strip_suffix(" left the game") {
+            online.remove(player);
+        }
+    }
+
+    // Without the startup line, players may have joined before the part of the log we have.
+    let Some(started_at) = started_at else {
+        return SessionStats::default();
+    };
+
+    // Log timestamps don't include the date, so assume the server ran for less than a day.
+    let uptime = stopped_at.map(|stopped_at| {
+        const DAY: u64 = 24 * 60 * 60;
+        std::time::Duration::from_secs((stopped_at + DAY - started_at) % DAY)
+    });
+
+    SessionStats {
+        uptime,
+        players_joined: Some(joined.len()),
+        peak_players: Some(peak),
+    }
+}
+
+/// Get the time a log line was written at, as the number of seconds since midnight.
+fn log_time(line: &str) -> Option<u64> {
+    let time = line.strip_prefix('[')?.get(..8)?;
+    let mut parts = time.split(':').map(|part| part.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (parts.next()??, parts.next()??, parts.next()??);
+
+    Some(hours * 60 * 60 + minutes * 60 + seconds)
+}
+
+/// Check whether `name` could be a player's name, so chat messages like `<Steve> joined the game`
+/// aren't counted as players joining.
+fn is_player_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(|c: char| c.is_whitespace() || c == '<' || c == '>')
+}
+
+/// Print the summary of the session that is shown after the server stops.
+fn print_session_stats(f: &mut impl Write, stats: &SessionStats) {
+    let unknown = || "unknown".to_owned();
+    let uptime = stats.uptime.map_or_else(unknown, |uptime| {
+        let minutes = uptime.as_secs() / 60;
+        match minutes / 60 {
+            0 => format!("{minutes}m {}s", uptime.as_secs() % 60),
+            hours => format!("{hours}h {}m", minutes % 60),
+        }
+    });
+    let players_joined = stats
+        .players_joined
+        .map_or_else(unknown, |count| count.to_string());
+    let peak_players = stats
+        .peak_players
+        .map_or_else(unknown, |count| count.to_string());
+
+    let separator = "-".repeat(78);
+    writeln!(f, "{separator}").ok();
+    writeln!(f, "{}: {uptime}", "Uptime".bold()).ok();
+    writeln!(f, "{}: {players_joined}", "Players joined".bold()).ok();
+    writeln!(f, "{}: {peak_players}", "Most concurrent players".bold()).ok();
+    writeln!(f, "{separator}").ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_stats() {
+        let log = "\
+[10:00:00] [ServerMain/INFO]: Environment: Environment[sessionHost=https://sessionserver.mojang.com]
+[10:00:01] [Server thread/INFO]: Starting minecraft server version 1.21.6
+[10:05:00] [Server thread/INFO]: Steve joined the game
+[10:06:00] [Server thread/INFO]: Alex joined the game
+[10:07:00] [Server thread/INFO]: Steve lost connection: Disconnected
+[10:07:00] [Server thread/INFO]: Steve left the game
+[10:08:00] [Server thread/INFO]: Steve joined the game
+[10:09:00] [Server thread/INFO]: <Alex> joined the game
+[12:15:01] [Server thread/INFO]: Stopping server";
+
+        assert_eq!(
+            session_stats(log.lines()),
+            SessionStats {
+                uptime: Some(std::time::Duration::from_secs(2 * 60 * 60 + 15 * 60)),
+                players_joined: Some(2),
+                peak_players: Some(2),
+            }
+        );
+    }
+
+    #[test]
+    fn test_session_stats_missing_data() {
+        // The server is stopped after midnight.
+        let log = "\
+[23:50:00] [Server thread/INFO]: Starting minecraft server version 1.21.6
+[00:10:00] [Server thread/INFO]: Stopping server";
+        let stats = session_stats(log.lines());
+        assert_eq!(stats.uptime, Some(std::time::Duration::from_secs(20 * 60)));
+        assert_eq!(stats.players_joined, Some(0));
+
+        // Without the startup line, nothing can be known for sure.
+        let log = "[10:05:00] [Server thread/INFO]: Steve joined the game";
+        assert_eq!(session_stats(log.lines()), SessionStats::default());
+    }
+}
@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::io::Write;
 
 use anyhow::Context;
@@ -9,72 +11,240 @@ pub struct Update {
     pub(crate) version: Option<String>,
 
     /// An incremental counter unique to each build that helps track the progress of releases.
+    #[arg(conflicts_with = "all")]
     pub(crate) build: Option<i64>,
 
+    /// Update every package created with `axiom new`, instead of only the current package.
+    ///
+    /// Without a version, each package is updated to the newest build of the Minecraft version it
+    /// already uses.
+    #[arg(long)]
+    pub(crate) all: bool,
+
     /// Upgrade to the latest version, even if the latest version is not yet stable.
     #[arg(long, short = 'e')]
     pub(crate) allow_experimental: bool,
 
-    /// Downgrade to an older version of Minecraft.
+    /// Downgrade to an older version of Minecraft, or an older build of the same version.
     #[arg(long, short = 'd')]
     pub(crate) allow_downgrade: bool,
 
     /// Seconds to wait before failing to download the new server JAR.
     #[arg(long, short = 't', default_value = "120")]
     pub(crate) timeout: u64,
+
+    /// Show which version and build would be installed, without downloading anything or
+    /// changing the package.
+    #[arg(long, short = 'n')]
+    pub(crate) dry_run: bool,
+
+    /// How many server JARs to download at the same time with `--all`. Defaults to the number of
+    /// CPUs, up to 4.
+    #[arg(long, short = 'j', default_value_t = default_jobs(), requires = "all")]
+    pub(crate) jobs: std::num::NonZeroUsize,
+}
+
+/// The most server JARs to download at the same time, unless `--jobs` says otherwise.
+const MAX_DEFAULT_JOBS: usize = 4;
+
+/// Get the default value for `--jobs`.
+fn default_jobs() -> std::num::NonZeroUsize {
+    let jobs = std::thread::available_parallelism()
+        .map_or(1, |cpus| cpus.get())
+        .min(MAX_DEFAULT_JOBS);
+    std::num::NonZeroUsize::new(jobs).unwrap_or(std::num::NonZeroUsize::MIN)
 }
 
 impl crate::commands::Run for Update {
     fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        if self.all {
+            return self.run_all(ctx);
+        }
+
+        super::validate_manifest_in(&ctx.package_directory()?)?;
+
         tracing::info!("getting supported Minecraft versions from PaperMC");
         let versions = ctx
             .versions()
             .with_context(|| "failed to get supported Minecraft versions from PaperMC")?;
+        let (version, build, paper_jar) = self.target(ctx, &versions, self.version.as_deref())?;
 
+        let package = ctx
+            .package()
+            .with_context(|| "failed to get package manifest")?;
+        self.check(ctx, &package, &version, &build, &versions)?;
+
+        if self.dry_run {
+            print_plan(ctx, &package, &version, &build, &paper_jar, &versions);
+            return Ok(());
+        }
+
+        if paper_jar.exists() {
+            tracing::info!("Using the server JAR that was already downloaded");
+        } else {
+            tracing::info!("Downloading build {}...", build.number());
+            let jars = ctx.jars().with_context(|| "failed to get server JARs")?;
+            std::fs::create_dir_all(&jars).with_context(|| "failed to create 'jars' directory")?;
+
+            build
+                .download_to(
+                    &paper_jar,
+                    std::time::Duration::from_secs(self.timeout),
+                    &mut report_progress(ctx, &build),
+                )
+                .map_err(|err| {
+                    crate::error::Error::new_with_hint(
+                        "run the same command to try again; interrupted downloads resume where they \
+                        left off",
+                        anyhow::Error::new(err).context("failed to download new server"),
+                    )
+                })?;
+        }
+
+        apply(&package, &version, &build, &paper_jar)?;
+
+        // TODO: The package's manifest and our `context` are now out of sync. In this case it's
+        // fine, because it's the end of the function, but I probably need to figure out a way to
+        // make the edits go through the context to ensure they are always updated together.
+
+        ctx.emit(crate::event::Event::Updated {
+            version: version.as_str().to_owned(),
+            build: build.number(),
+        });
+
+        if !ctx.porcelain() {
+            let mut stderr = std::io::stderr().lock();
+            writeln!(
+                stderr,
+                "✨ server updated to Minecraft version {} (#{})",
+                version.as_str(),
+                build.number()
+            )
+            .ok();
+        }
+
+        Ok(())
+    }
+}
+
+/// Describes what happened to one of the packages updated by `update --all`.
+enum Outcome {
+    /// The package was switched to the new server JAR.
+    Updated,
+    /// The package would be switched to the new server JAR, if not for `--dry-run`.
+    WouldUpdate,
+    /// The package was already using the new server JAR.
+    UpToDate,
+    /// The package could not be updated.
+    Failed(crate::error::Error),
+}
+
+impl Update {
+    /// Pick the build of `version` (or of the latest version) to update to, and where its server
+    /// JAR is in the cache.
+    fn target(
+        &self,
+        ctx: &mut crate::context::Context,
+        versions: &[axiom::paper::Version],
+        version: Option<&str>,
+    ) -> Result<
+        (
+            axiom::paper::Version,
+            axiom::paper::Build,
+            std::path::PathBuf,
+        ),
+        crate::error::Error,
+    > {
         // Check if the version provided is a valid version.
-        let version = match self.version.as_ref() {
-            Some(version) => versions
-                .iter()
-                .find(|&v| version == v.as_str())
-                .with_context(|| "version not supported")?,
-            None => versions
-                .last()
-                .with_context(|| "no supported versions available")?,
+        let version = match version {
+            Some(version) => match versions.iter().find(|&v| version == v.as_str()) {
+                Some(version) => version,
+                None if ctx.offline() => {
+                    let message = format!("version {version} has not been downloaded");
+                    let hint = cached_builds_hint(ctx)?;
+                    return Err(crate::error::Error::new_with_hint(hint, message));
+                }
+                None => {
+                    crate::bail!("version not supported");
+                }
+            },
+            None => match versions.last() {
+                Some(version) => version,
+                None if ctx.offline() => {
+                    let hint = cached_builds_hint(ctx)?;
+                    let message = "no versions are available offline";
+                    return Err(crate::error::Error::new_with_hint(hint, message));
+                }
+                None => {
+                    crate::bail!("no supported versions available");
+                }
+            },
         };
 
         // Check if the build provided is a valid build.
         let build = match self.build.as_ref() {
-            Some(build) => axiom::paper::Build::new(
-                version.as_str().to_owned(),
-                *build,
-                // The `Default` channel indicates a stable build, which will bypass certain
-                // validation checks. This is desired because in some cases we are the caller,
-                // and we don't want to make multiple calls to the PaperMC API to verify
-                // information that was already verified.
-                axiom::paper::Channel::Default,
-                format!("paper-{version}-{build}.jar", version = version.as_str()),
-            ),
-            None => version
-                .builds()
+            Some(&build) => {
+                let download_name = format!("paper-{}-{build}.jar", version.as_str());
+                let jars = ctx.jars().with_context(|| "failed to get server JARs")?;
+
+                // A cached JAR was already verified when it was downloaded, and there is nothing
+                // to verify in offline mode, so avoid making another call to the PaperMC API.
+                if jars.join(&download_name).exists() || ctx.offline() {
+                    axiom::paper::Build::new(
+                        version.as_str().to_owned(),
+                        build,
+                        // The `Default` channel indicates a stable build, which will bypass
+                        // certain validation checks.
+                        axiom::paper::Channel::Default,
+                        download_name,
+                    )
+                } else {
+                    find_build(ctx, version, build)?
+                }
+            }
+            None => ctx
+                .builds(version)
                 .with_context(|| "failed to get builds")?
                 .pop()
                 .with_context(|| "no builds available for selected version")?,
         };
 
-        let package = ctx
-            .package()
-            .with_context(|| "failed to get package manifest")?;
+        let jars = ctx.jars().with_context(|| "failed to get server JARs")?;
+        let paper_jar = jars.join(build.download_name());
 
-        // If the user is already using an experimental build, bypass the safe upgrade check.
-        let allow_experimental = if build.experimental()
-            && (version.as_str() == package.manifest().server().version())
-        {
-            true
-        } else {
-            self.allow_experimental
-        };
+        if ctx.offline() && !paper_jar.exists() {
+            let message = format!(
+                "build {} for version {} has not been downloaded",
+                build.number(),
+                version.as_str()
+            );
+            let hint = cached_builds_hint(ctx)?;
+            return Err(crate::error::Error::new_with_hint(hint, message));
+        }
+
+        Ok((version.to_owned(), build, paper_jar))
+    }
 
-        if build.experimental() && !allow_experimental {
+    /// Make sure `package` is allowed to switch to `build` of `version`, given the
+    /// `--allow-experimental` and `--allow-downgrade` flags.
+    fn check(
+        &self,
+        ctx: &mut crate::context::Context,
+        package: &axiom::Package,
+        version: &axiom::paper::Version,
+        build: &axiom::paper::Build,
+        versions: &[axiom::paper::Version],
+    ) -> Result<(), crate::error::Error> {
+        // If the user is already using an experimental build, bypass the safe upgrade check.
+        // Builds from channels Axiom doesn't know about are treated as experimental too.
+        let allow_experimental =
+            if !build.stable() && (version.as_str() == package.manifest().server().version()) {
+                true
+            } else {
+                self.allow_experimental
+            };
+
+        if !build.stable() && !allow_experimental {
             let message = format!(
                 "selected version is experimental. use {} or set a stable version explicitly",
                 "--allow-experimental".yellow()
@@ -82,7 +252,7 @@ impl crate::commands::Run for Update {
 
             let err = crate::error::Error::new(anyhow::anyhow!(message));
 
-            if let Ok(stable_version) = get_latest_stable_version(&versions, version) {
+            if let Ok(stable_version) = get_latest_stable_version(ctx, versions, version) {
                 let hint = format!("The latest stable version is '{}'", stable_version.as_str());
                 return Err(err.with_hint(|| hint));
             }
@@ -94,84 +264,434 @@ impl crate::commands::Run for Update {
         if !self.allow_downgrade {
             tracing::info!("Checking which version is currently installed");
 
-            if let Ok(current_version) = package.server().build_info() {
-                ensure_no_downgrade(
-                    &axiom::paper::Version::new(current_version.version().to_owned()),
-                    version,
-                )?;
+            match package.server().installed_build_info() {
+                Ok(current) => ensure_no_downgrade(&current, version, build, versions)?,
+                // Nothing to downgrade from if the server hasn't been built yet.
+                Err(axiom::package::ServerBuildInfoError::ServerJarNotFound { .. }) => {}
+                Err(err) => {
+                    tracing::warn!("failed to check which version is currently installed: {err}")
+                }
             }
         }
 
-        let jars = ctx.jars().with_context(|| "failed to get server JARs")?;
-        let paper_jar = jars.join(build.download_name());
+        Ok(())
+    }
 
-        if paper_jar.exists() {
-            tracing::info!("Already using the latest build");
-        } else {
-            tracing::info!("Downloading the latest build...");
+    /// Update every registered package to the same version and build, then print a table of the
+    /// results.
+    ///
+    /// A package that can't be updated doesn't stop the others from being updated; instead, the
+    /// command fails at the end.
+    fn run_all(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let paths =
+            crate::registry::all().with_context(|| "failed to read the package registry")?;
+
+        if paths.is_empty() {
+            return Err(crate::error::Error::new_with_hint(
+                "packages are registered when they are created with `axiom new`",
+                anyhow::anyhow!("no packages have been registered"),
+            ));
+        }
 
-            let data = build
-                .download(std::time::Duration::from_secs(self.timeout))
-                .with_context(|| "failed to download new server")?;
+        // The version and build lists are fetched once, and shared by every package.
+        tracing::info!("getting supported Minecraft versions from PaperMC");
+        let versions = ctx
+            .versions()
+            .with_context(|| "failed to get supported Minecraft versions from PaperMC")?;
+        let shared_target = match &self.version {
+            Some(version) => Some(self.target(ctx, &versions, Some(version))?),
+            None => None,
+        };
 
-            std::fs::create_dir_all(jars).with_context(|| "failed to create 'jars' directory")?;
-            std::fs::write(&paper_jar, &data).with_context(|| "failed to save new server")?;
+        let mut results = Vec::with_capacity(paths.len());
+        let mut pending = Vec::new();
+
+        for path in paths {
+            let package = super::validate_manifest_in(&path).and_then(|()| {
+                axiom::Manifest::from_directory(&path)
+                    .map(|manifest| axiom::Package::new(path.to_owned(), manifest))
+                    .map_err(crate::error::Error::new)
+            });
+            let package = match package {
+                Ok(package) => package,
+                Err(err) => {
+                    results.push((
+                        path.display().to_string(),
+                        String::new(),
+                        Outcome::Failed(err),
+                    ));
+                    continue;
+                }
+            };
+
+            let name = package.name().to_owned();
+
+            // Without a version, each package gets the newest build of the version it is on.
+            let target = match &shared_target {
+                Some(target) => Ok(target.to_owned()),
+                None => self.target(ctx, &versions, Some(package.manifest().server().version())),
+            };
+            let (version, build, _) = match target {
+                Ok(target) => target,
+                Err(err) => {
+                    results.push((name, String::new(), Outcome::Failed(err)));
+                    continue;
+                }
+            };
+
+            let details = format!("{} (#{})", version.as_str(), build.number());
+            let up_to_date = package
+                .server()
+                .installed_build_info()
+                .is_ok_and(|current| {
+                    (current.version(), current.build()) == (version.as_str(), build.number())
+                });
+
+            let outcome = match self.check(ctx, &package, &version, &build, &versions) {
+                Err(err) => Outcome::Failed(err),
+                Ok(()) if up_to_date => Outcome::UpToDate,
+                Ok(()) if self.dry_run => Outcome::WouldUpdate,
+                Ok(()) => {
+                    pending.push((results.len(), package, version, build));
+                    Outcome::Updated
+                }
+            };
+            results.push((name, details, outcome));
         }
 
-        assert!(&package.server().path().exists());
-        let server_jar = package.server().server_jar();
+        if !pending.is_empty() {
+            let jars = ctx.jars().with_context(|| "failed to get server JARs")?;
+            std::fs::create_dir_all(&jars).with_context(|| "failed to create 'jars' directory")?;
 
-        if let Err(err) = std::fs::remove_file(server_jar) {
-            match err.kind() {
-                std::io::ErrorKind::NotFound => (), // No file to remove.
-                std::io::ErrorKind::IsADirectory => std::fs::remove_dir_all(server_jar)
-                    .with_context(|| "failed to remove server.jar directory")?,
-                _ => return Err(err).with_context(|| "failed to remove existing server")?,
+            let builds = pending
+                .iter()
+                .map(|(_, _, _, build)| build.clone())
+                .collect::<Vec<_>>();
+            let downloads = self.download_all(ctx, &builds, &jars);
+
+            for (i, package, version, build) in pending {
+                let result = match &downloads[build.download_name()] {
+                    Ok(paper_jar) => apply(&package, &version, &build, paper_jar),
+                    Err(err) => Err(crate::error::Error::new(anyhow::anyhow!(
+                        "failed to download new server: {err}"
+                    ))),
+                };
+
+                if let Err(err) = result {
+                    results[i].2 = Outcome::Failed(err);
+                }
             }
         }
 
-        symlink::symlink_file(&paper_jar, server_jar)
-            .with_context(|| "failed to link new server.jar")?;
+        print_results(ctx, &results);
 
-        // Even though we already read the package manifest in `package`, we need the raw manifest
-        // contents in order to edit the file while preserving the user's comments.
-        let manifest_content = std::fs::read_to_string(package.manifest_path())
-            .with_context(|| "failed to read manifest")?;
-        let mut document = manifest_content
-            .parse::<toml_edit::DocumentMut>()
-            .with_context(|| "failed to parse manifest")?;
+        let failed = results
+            .iter()
+            .filter(|(_, _, outcome)| matches!(outcome, Outcome::Failed(_)))
+            .count();
 
-        document["server"]["version"] = toml_edit::value(version.as_str());
-        document["server"]["build"] = toml_edit::value(build.number());
+        if failed > 0 {
+            crate::bail!("failed to update {failed} of {} packages", results.len());
+        }
 
-        std::fs::write(package.manifest_path(), document.to_string())
-            .with_context(|| "failed to set new version and build in the manifest")?;
+        Ok(())
+    }
 
-        // TODO: The package's manifest and our `context` are now out of sync. In this case it's
-        // fine, because it's the end of the function, but I probably need to figure out a way to
-        // make the edits go through the context to ensure they are always updated together.
+    /// Download the server JAR for each of `builds` into `jars`, up to `--jobs` at a time.
+    ///
+    /// JARs that are already in the cache are skipped. The results are keyed by each build's
+    /// download name, so packages that need the same build share a single download, and one
+    /// failed download doesn't stop the others.
+    fn download_all(
+        &self,
+        ctx: &crate::context::Context,
+        builds: &[axiom::paper::Build],
+        jars: &std::path::Path,
+    ) -> HashMap<String, Result<std::path::PathBuf, axiom::paper::RequestError>> {
+        let timeout = std::time::Duration::from_secs(self.timeout);
+
+        download_concurrently(builds, self.jobs.get(), &|build| {
+            let paper_jar = jars.join(build.download_name());
+
+            if !paper_jar.exists() {
+                tracing::info!(
+                    "downloading build {} of version {}",
+                    build.number(),
+                    build.version()
+                );
+                build.download_to(&paper_jar, timeout, &mut report_progress(ctx, build))?;
+            }
 
-        let mut stderr = std::io::stderr().lock();
-        writeln!(
-            stderr,
-            "✨ server updated to Minecraft version {} (#{})",
-            version.as_str(),
-            build.number()
-        )
-        .ok();
+            Ok(paper_jar)
+        })
+    }
+}
 
-        Ok(())
+/// Get a callback for [`axiom::paper::Build::download_to`] that emits a download event each time
+/// another percent of `build` has been downloaded.
+fn report_progress<'a>(
+    ctx: &'a crate::context::Context,
+    build: &'a axiom::paper::Build,
+) -> impl FnMut(u64, Option<u64>) + 'a {
+    let mut last_pct = None;
+
+    move |received, total| {
+        let Some(total) = total.filter(|&total| total > 0) else {
+            return;
+        };
+        let pct = (received.min(total) * 100 / total) as u8;
+
+        if last_pct != Some(pct) {
+            last_pct = Some(pct);
+            ctx.emit(crate::event::Event::Download {
+                jar: build.download_name().to_owned(),
+                pct,
+            });
+        }
+    }
+}
+
+/// Point the package's `server.jar` at `paper_jar`, and record the new version and build in its
+/// manifest.
+fn apply(
+    package: &axiom::Package,
+    version: &axiom::paper::Version,
+    build: &axiom::paper::Build,
+    paper_jar: &std::path::Path,
+) -> Result<(), crate::error::Error> {
+    if !package.server().path().is_dir() {
+        return Err(crate::error::Error::new_with_hint(
+            "run `axiom build` to set up the server",
+            anyhow::anyhow!("{} does not exist", package.server().path().display()),
+        ));
     }
+
+    let server_jar = package.server().server_jar();
+
+    // `rename` can't replace a directory, so that has to be removed first.
+    if server_jar.is_dir() && !server_jar.is_symlink() {
+        std::fs::remove_dir_all(server_jar)
+            .with_context(|| "failed to remove server.jar directory")?;
+    }
+
+    replace_symlink(paper_jar, server_jar).with_context(|| "failed to link new server.jar")?;
+
+    // Even though we already read the package manifest in `package`, we need the raw manifest
+    // contents in order to edit the file while preserving the user's comments.
+    let manifest_content = std::fs::read_to_string(package.manifest_path())
+        .with_context(|| "failed to read manifest")?;
+    let mut document = manifest_content
+        .parse::<toml_edit::DocumentMut>()
+        .with_context(|| "failed to parse manifest")?;
+
+    document["server"]["version"] = toml_edit::value(version.as_str());
+    document["server"]["build"] = toml_edit::value(build.number());
+
+    std::fs::write(package.manifest_path(), document.to_string())
+        .with_context(|| "failed to set new version and build in the manifest")?;
+
+    Ok(())
+}
+
+/// Print a table of what happened to each package, for the `--all` flag.
+///
+/// Each result is the package's name, the version and build it was updated to, and the outcome.
+fn print_results(ctx: &crate::context::Context, results: &[(String, String, Outcome)]) {
+    let name_width = results
+        .iter()
+        .map(|(name, _, _)| name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("NAME".len());
+
+    let mut stdout = ctx.output();
+    writeln!(
+        stdout,
+        "{:name_width$}  {:12}  {}",
+        "NAME".bold(),
+        "RESULT".bold(),
+        "DETAILS".bold()
+    )
+    .ok();
+
+    for (name, target, outcome) in results {
+        let (result, details) = match outcome {
+            Outcome::Updated => ("updated".green(), target.to_owned()),
+            Outcome::WouldUpdate => ("would update".yellow(), target.to_owned()),
+            Outcome::UpToDate => ("up to date".normal(), target.to_owned()),
+            Outcome::Failed(err) => ("failed".red(), err.to_string()),
+        };
+
+        writeln!(stdout, "{name:name_width$}  {result:12}  {details}").ok();
+    }
+}
+
+/// Call `download` once for each unique build in `builds`, on up to `jobs` threads at a time.
+///
+/// Builds are told apart by their download name (e.g., `paper-1.21.6-34.jar`), which is also the
+/// key of each result.
+fn download_concurrently<T, E>(
+    builds: &[axiom::paper::Build],
+    jobs: usize,
+    download: &(dyn Fn(&axiom::paper::Build) -> Result<T, E> + Sync),
+) -> HashMap<String, Result<T, E>>
+where
+    T: Send,
+    E: Send,
+{
+    let mut unique = HashMap::new();
+
+    for build in builds {
+        unique.entry(build.download_name()).or_insert(build);
+    }
+
+    let workers = jobs.clamp(1, unique.len().max(1));
+    let queue = std::sync::Mutex::new(unique.into_values());
+    let results = std::sync::Mutex::new(HashMap::new());
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    // Release the lock before downloading, so the other threads can take the
+                    // next build in the meantime.
+                    let Some(build) = queue.lock().unwrap().next() else {
+                        break;
+                    };
+                    let result = download(build);
+                    results
+                        .lock()
+                        .unwrap()
+                        .insert(build.download_name().to_owned(), result);
+                }
+            });
+        }
+    });
+
+    results.into_inner().unwrap()
+}
+
+/// Find `number` in the builds PaperMC has released for `version`.
+fn find_build(
+    ctx: &mut crate::context::Context,
+    version: &axiom::paper::Version,
+    number: i64,
+) -> Result<axiom::paper::Build, crate::error::Error> {
+    let builds = ctx
+        .builds(version)
+        .with_context(|| "failed to get builds")?;
+
+    if let Some(build) = builds.iter().find(|build| build.number() == number) {
+        return Ok(build.clone());
+    }
+
+    let message = format!("build {number} not found for version {}", version.as_str());
+
+    let mut nearest = builds
+        .iter()
+        .map(|build| build.number())
+        .collect::<Vec<_>>();
+    nearest.sort_by_key(|&build| (build - number).abs());
+    nearest.truncate(3);
+    nearest.sort();
+
+    if nearest.is_empty() {
+        crate::bail!(message);
+    }
+
+    let nearest = nearest
+        .iter()
+        .map(|build| build.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let hint = format!("the nearest builds for this version are: {nearest}");
+
+    Err(crate::error::Error::new_with_hint(hint, message))
+}
+
+/// Describe which builds can be used in offline mode.
+fn cached_builds_hint(ctx: &mut crate::context::Context) -> Result<String, crate::error::Error> {
+    let jars = ctx.jars().with_context(|| "failed to get server JARs")?;
+    let builds = axiom::paper::cached_builds(&jars);
+
+    if builds.is_empty() {
+        return Ok(format!(
+            "no server JARs have been downloaded yet; try again without {}",
+            "--offline".yellow()
+        ));
+    }
+
+    let builds = builds
+        .iter()
+        .map(|build| format!("{} (#{})", build.version(), build.number()))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Ok(format!(
+        "The following builds are available offline: {builds}"
+    ))
+}
+
+/// Print what `update` would do, for the `--dry-run` flag.
+fn print_plan(
+    ctx: &crate::context::Context,
+    package: &axiom::Package,
+    version: &axiom::paper::Version,
+    build: &axiom::paper::Build,
+    paper_jar: &std::path::Path,
+    versions: &[axiom::paper::Version],
+) {
+    let mut stdout = ctx.output();
+    let current = package.server().installed_build_info().ok();
+
+    let current_version = match &current {
+        Some(current) => format!("{} (#{})", current.version(), current.build()),
+        None => "not built".to_owned(),
+    };
+    writeln!(stdout, "{}: {}", "Current Version".bold(), current_version).ok();
+
+    let target_version = format!("{} (#{})", version.as_str(), build.number());
+    writeln!(stdout, "{}: {}", "Target Version".bold(), target_version).ok();
+
+    let channel = match build.channel() {
+        axiom::paper::Channel::Default => "stable".green(),
+        channel => channel.to_string().yellow(),
+    };
+    writeln!(stdout, "{}: {}", "Channel".bold(), channel).ok();
+
+    let downgrade = current.as_ref().is_some_and(|current| {
+        cmp_installed(current, version, build.number(), versions) == Some(Ordering::Greater)
+    });
+    let downgrade = if downgrade {
+        "yes".yellow()
+    } else {
+        "no".normal()
+    };
+    writeln!(stdout, "{}: {}", "Downgrade".bold(), downgrade).ok();
+
+    let cached = match paper_jar.exists() {
+        true => format!("yes ({})", paper_jar.display()),
+        false => "no".to_owned(),
+    };
+    writeln!(stdout, "{}: {}", "Cached".bold(), cached).ok();
+
+    let up_to_date = current.as_ref().is_some_and(|current| {
+        (current.version(), current.build()) == (version.as_str(), build.number())
+    });
+    let action = match (up_to_date, paper_jar.exists()) {
+        (true, _) => "nothing to do",
+        (false, true) => "link the cached server JAR and update Axiom.toml",
+        (false, false) => "download the server JAR and update Axiom.toml",
+    };
+    writeln!(stdout, "{}: {}", "Action".bold(), action).ok();
 }
 
 // Due to the long interval between Minecraft version releases, we typically see only one
 // additional API call as the previous version usually stabilizes by the time a new one is
 // released. However, this function can technically call the API multiple times if consecutive
-// releases do not reach a stable status.
-//
-// TODO: It would be a good idea to limit the number of calls we can make or to cache information
-// that will allow us to determine the latest stable version locally.
+// releases do not reach a stable status (the builds are cached, so only the first run does).
 fn get_latest_stable_version(
+    ctx: &mut crate::context::Context,
     supported_versions: &[axiom::paper::Version],
     selected: &axiom::paper::Version,
 ) -> Result<axiom::paper::Version, anyhow::Error> {
@@ -181,8 +701,8 @@ fn get_latest_stable_version(
         .collect();
 
     while let Some(version) = older_versions.pop() {
-        let build = version
-            .builds()
+        let build = ctx
+            .builds(version)
             .with_context(|| "failed to get builds")?
             .pop()
             .with_context(|| "failed to get latest build")?;
@@ -195,28 +715,145 @@ fn get_latest_stable_version(
     Err(anyhow::anyhow!("failed to find the latest stable version"))
 }
 
+/// Compare the installed build to build `number` of `version`.
+///
+/// Builds of the same version are compared by their build number. Returns `None` if the order of
+/// the Minecraft versions is unknown (see [`axiom::paper::Version::cmp_release`]).
+fn cmp_installed(
+    current: &axiom::package::ServerBuildInfo,
+    version: &axiom::paper::Version,
+    number: i64,
+    versions: &[axiom::paper::Version],
+) -> Option<Ordering> {
+    let before = axiom::paper::Version::new(current.version().to_owned());
+
+    match before.cmp_release(version, versions)? {
+        Ordering::Equal => Some(current.build().cmp(&number)),
+        ordering => Some(ordering),
+    }
+}
+
 fn ensure_no_downgrade(
-    before: &axiom::paper::Version,
+    current: &axiom::package::ServerBuildInfo,
     after: &axiom::paper::Version,
+    build: &axiom::paper::Build,
+    versions: &[axiom::paper::Version],
 ) -> Result<(), crate::error::Error> {
-    let before = semver::Version::parse(before.as_str())
-        .expect("expected `before` to follow semantic versioning");
-    let after = semver::Version::parse(after.as_str())
-        .expect("expected `after` to follow semantic versioning");
-
-    if let std::cmp::Ordering::Greater = before.cmp(&after) {
-        let message = format!(
-            "the selected version ({}) is older than the current version ({})",
-            after, before
+    let Some(ordering) = cmp_installed(current, after, build.number(), versions) else {
+        tracing::warn!(
+            "failed to check whether {} is older than {}",
+            after.as_str(),
+            current.version()
         );
+        return Ok(());
+    };
+
+    if let Ordering::Greater = ordering {
+        let same_version = current.version() == after.as_str();
+        let message = if same_version {
+            format!(
+                "the selected build (#{}) is older than the current build (#{}) of version {}",
+                build.number(),
+                current.build(),
+                after.as_str()
+            )
+        } else {
+            format!(
+                "the selected version ({}) is older than the current version ({})",
+                after.as_str(),
+                current.version()
+            )
+        };
 
         let hint = format!(
-            "try again with {} or use a different version",
-            "--allow-downgrade".yellow()
+            "try again with {} or use a different {}",
+            "--allow-downgrade".yellow(),
+            if same_version { "build" } else { "version" }
         );
 
-        return Err(crate::error::Error::new_with_hint(message, hint));
+        return Err(crate::error::Error::new_with_hint(hint, message));
     }
 
     Ok(())
 }
+
+/// Point the symlink at `link` to `target`, replacing whatever is there.
+///
+/// The new symlink is created next to `link` and renamed over it, so there is never a moment
+/// where `link` is missing (e.g., if Axiom is interrupted halfway through).
+fn replace_symlink(target: &std::path::Path, link: &std::path::Path) -> std::io::Result<()> {
+    let mut temp_name = link.file_name().unwrap_or_default().to_owned();
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+    let temp = link.with_file_name(temp_name);
+
+    // Clean up after a previous attempt that was interrupted.
+    let _ = std::fs::remove_file(&temp);
+    symlink::symlink_file(target, &temp)?;
+
+    std::fs::rename(&temp, link).inspect_err(|_| {
+        let _ = std::fs::remove_file(&temp);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cmp_installed() {
+        let versions = ["1.21.4", "1.21.6"].map(|v| axiom::paper::Version::new(v.to_owned()));
+        let current = axiom::package::ServerBuildInfo::new("1.21.6".to_owned(), 34, None);
+
+        let cmp = |version: &axiom::paper::Version, number| {
+            cmp_installed(&current, version, number, &versions)
+        };
+
+        assert_eq!(cmp(&versions[1], 30), Some(Ordering::Greater));
+        assert_eq!(cmp(&versions[1], 34), Some(Ordering::Equal));
+        assert_eq!(cmp(&versions[1], 40), Some(Ordering::Less));
+        // The Minecraft version takes priority over the build number.
+        assert_eq!(cmp(&versions[0], 100), Some(Ordering::Greater));
+    }
+
+    #[test]
+    fn test_download_concurrently() {
+        let build = |version: &str, number| {
+            axiom::paper::Build::new(
+                version.to_owned(),
+                number,
+                axiom::paper::Channel::Default,
+                format!("paper-{version}-{number}.jar"),
+            )
+        };
+        let builds = [
+            build("1.21.6", 34),
+            build("1.21.4", 232),
+            build("1.21.6", 34),
+            build("1.21.6", 48),
+        ];
+
+        let running = std::sync::atomic::AtomicUsize::new(0);
+        let most_running = std::sync::atomic::AtomicUsize::new(0);
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+
+        let results = download_concurrently(&builds, 2, &|build| {
+            use std::sync::atomic::Ordering::SeqCst;
+
+            calls.fetch_add(1, SeqCst);
+            most_running.fetch_max(running.fetch_add(1, SeqCst) + 1, SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            running.fetch_sub(1, SeqCst);
+
+            match build.number() {
+                232 => Err("connection reset"),
+                number => Ok(number),
+            }
+        });
+
+        assert_eq!(calls.into_inner(), 3);
+        assert!(most_running.into_inner() <= 2);
+        assert_eq!(results["paper-1.21.6-34.jar"], Ok(34));
+        assert_eq!(results["paper-1.21.6-48.jar"], Ok(48));
+        assert_eq!(results["paper-1.21.4-232.jar"], Err("connection reset"));
+    }
+}
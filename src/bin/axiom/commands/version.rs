@@ -0,0 +1,55 @@
+//! This module implements the `version` command, which prints the versions of Axiom and the
+//! current package's server in a terse format that can be pasted into bug reports.
+
+use std::io::Write;
+
+use anyhow::Context;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Version;
+
+impl crate::commands::Run for Version {
+    fn run(&self, ctx: &mut crate::context::Context) -> Result<(), crate::error::Error> {
+        let mut stdout = ctx.output();
+        writeln!(stdout, "axiom: {}", env!("CARGO_PKG_VERSION")).ok();
+
+        // Outside of a package, there is no server to report on.
+        if ctx.package_directory().is_err() {
+            return Ok(());
+        }
+
+        let package = ctx
+            .package()
+            .with_context(|| "failed to get package manifest")?;
+        let server = package.manifest().server();
+        writeln!(stdout, "server: {} (#{})", server.version(), server.build()).ok();
+
+        let server = package.server();
+        if !server.server_jar().exists() {
+            writeln!(stdout, "installed: not built").ok();
+            return Ok(());
+        }
+
+        match server.installed_build_info() {
+            Ok(build_info) => {
+                writeln!(
+                    stdout,
+                    "installed: {} (#{})",
+                    build_info.version(),
+                    build_info.build()
+                )
+                .ok();
+
+                if let Some(commit_hash) = build_info.commit_hash() {
+                    writeln!(stdout, "commit: {commit_hash}").ok();
+                }
+            }
+            Err(err) => {
+                tracing::warn!("failed to get build information for current server JAR: {err}");
+                writeln!(stdout, "installed: unknown").ok();
+            }
+        }
+
+        Ok(())
+    }
+}
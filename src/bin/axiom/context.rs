@@ -1,40 +1,250 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use anyhow::Context as _;
 
+use crate::backend::{Backend, SystemdBackend, TmuxBackend};
+
 #[derive(Debug, Clone, Default)]
 pub struct Context {
-    versions: Option<Rc<[axiom::paper::Version]>>,
-    jars: Option<Rc<std::path::Path>>,
-    package: Option<Rc<axiom::Package>>,
+    versions: Option<Arc<[axiom::paper::Version]>>,
+    jars: Option<Arc<std::path::Path>>,
+    package: Option<Arc<axiom::Package>>,
+    directory: Option<std::path::PathBuf>,
+    tmux_socket: Option<String>,
+    json: bool,
+    offline: bool,
+    quiet: bool,
+    porcelain: bool,
 }
 
 impl Context {
-    pub fn versions(&mut self) -> Result<Rc<[axiom::paper::Version]>, anyhow::Error> {
+    /// Look for the package in `directory`, instead of the current directory.
+    pub fn with_directory(self, directory: Option<std::path::PathBuf>) -> Self {
+        Self { directory, ..self }
+    }
+
+    /// Get the directory to look for the package in.
+    ///
+    /// This is the directory passed to `--dir`, or the current directory otherwise.
+    pub fn directory(&self) -> Result<std::path::PathBuf, anyhow::Error> {
+        match &self.directory {
+            Some(directory) => Ok(directory.to_owned()),
+            None => std::env::current_dir().with_context(|| "failed to get current directory"),
+        }
+    }
+
+    /// Get the root directory of the package, which contains the manifest.
+    ///
+    /// The package is searched for starting at [`Self::directory`] and walking up through its
+    /// parent directories, so commands also work from inside of the package's subdirectories.
+    pub fn package_directory(&self) -> Result<std::path::PathBuf, anyhow::Error> {
+        let start = self.directory()?;
+
+        let Some(manifest_path) = axiom::manifest::find_manifest_upward(&start) else {
+            let searched = start
+                .ancestors()
+                .map(|directory| directory.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            anyhow::bail!("could not find {} in {searched}", axiom::Manifest::FILENAME);
+        };
+
+        let directory = manifest_path
+            .parent()
+            .expect("expected manifest path to have a parent directory");
+        Ok(directory.to_path_buf())
+    }
+
+    /// Use a different tmux socket name than [`crate::commands::TMUX_SERVER_NAME`].
+    pub fn with_tmux_socket(self, tmux_socket: Option<String>) -> Self {
+        Self {
+            tmux_socket,
+            ..self
+        }
+    }
+
+    /// Prefer JSON over human-readable output.
+    pub fn with_json(self, json: bool) -> Self {
+        Self { json, ..self }
+    }
+
+    /// Check whether output should be printed as JSON instead of in a human-readable format.
+    pub fn json(&self) -> bool {
+        self.json
+    }
+
+    /// Skip printing status messages that aren't needed to understand the result of a command.
+    pub fn with_quiet(self, quiet: bool) -> Self {
+        Self { quiet, ..self }
+    }
+
+    /// Check whether non-essential status messages should be left out.
+    ///
+    /// Errors, warnings, and a command's actual output (e.g., tables, summaries, or the line
+    /// saying what the command did) are always printed. Status messages are left out with
+    /// `--porcelain` too, which prints events instead.
+    pub fn quiet(&self) -> bool {
+        self.quiet || self.porcelain
+    }
+
+    /// Print progress as JSON events, instead of human-readable status messages.
+    pub fn with_porcelain(self, porcelain: bool) -> Self {
+        Self { porcelain, ..self }
+    }
+
+    /// Check whether progress is printed as JSON events, in which case the human-readable result
+    /// of a command is left out too.
+    pub fn porcelain(&self) -> bool {
+        self.porcelain
+    }
+
+    /// Get where a command's results (e.g., tables or summaries) should be printed.
+    ///
+    /// This is stdout, unless `--porcelain` was passed, in which case stdout is reserved for
+    /// events and the results are printed to stderr instead.
+    pub fn output(&self) -> Box<dyn std::io::Write> {
+        match self.porcelain {
+            true => Box::new(std::io::stderr().lock()),
+            false => Box::new(std::io::stdout().lock()),
+        }
+    }
+
+    /// Print `event` if `--porcelain` was passed.
+    pub fn emit(&self, event: crate::event::Event) {
+        if self.porcelain {
+            event.emit();
+        }
+    }
+
+    /// Only use data that is already on disk, instead of making requests to PaperMC.
+    pub fn with_offline(self, offline: bool) -> Self {
+        Self { offline, ..self }
+    }
+
+    /// Use `versions` instead of asking PaperMC for the supported Minecraft versions (e.g., so
+    /// commands can be tested without a network connection).
+    #[cfg(test)]
+    pub fn with_versions(self, versions: Vec<axiom::paper::Version>) -> Self {
+        Self {
+            versions: Some(versions.into()),
+            ..self
+        }
+    }
+
+    /// Check whether requests to PaperMC should be avoided.
+    pub fn offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Get the tmux session that all of the Minecraft servers run in.
+    pub fn tmux_session(&self) -> axiom::tmux::Session {
+        let server = self
+            .tmux_socket
+            .as_deref()
+            .unwrap_or(crate::commands::TMUX_SERVER_NAME);
+
+        axiom::tmux::Session::new(
+            server.to_owned(),
+            crate::commands::TMUX_SESSION_NAME.to_owned(),
+        )
+    }
+
+    pub fn versions(&mut self) -> Result<Arc<[axiom::paper::Version]>, anyhow::Error> {
         match &self.versions {
-            Some(versions) => Ok(Rc::clone(versions)),
+            Some(versions) => Ok(Arc::clone(versions)),
             None => {
-                let versions = axiom::paper::versions()
-                    .with_context(|| "failed to get supported Minecraft versions from PaperMC")?;
+                let versions = if self.offline {
+                    let jars = self.jars()?;
+                    axiom::paper::cached_versions(&jars)
+                } else {
+                    axiom::paper::versions().with_context(
+                        || "failed to get supported Minecraft versions from PaperMC",
+                    )?
+                };
                 self.versions = Some(versions.into());
-                Ok(Rc::clone(self.versions.as_ref().unwrap()))
+                Ok(Arc::clone(self.versions.as_ref().unwrap()))
             }
         }
     }
 
-    pub fn jars(&mut self) -> Result<Rc<std::path::Path>, anyhow::Error> {
+    /// Get all of the available builds for `version`, oldest to newest.
+    ///
+    /// The list of builds is cached for a few minutes (see [`axiom::paper::BUILDS_CACHE_TTL`]), so
+    /// commands that look up builds several times in a row only contact PaperMC once. In offline
+    /// mode, only the builds that have already been downloaded are available.
+    pub fn builds(
+        &mut self,
+        version: &axiom::paper::Version,
+    ) -> Result<Vec<axiom::paper::Build>, anyhow::Error> {
+        if self.offline {
+            let jars = self.jars()?;
+            let builds = axiom::paper::cached_builds(&jars)
+                .into_iter()
+                .filter(|build| build.version() == version.as_str())
+                .collect();
+            return Ok(builds);
+        }
+
+        let jars = self.jars()?;
+        version
+            .builds_cached(&jars)
+            .with_context(|| "failed to get builds from PaperMC")
+    }
+
+    pub fn jars(&mut self) -> Result<Arc<std::path::Path>, anyhow::Error> {
         match &self.jars {
-            Some(jars) => Ok(Rc::clone(jars)),
+            Some(jars) => Ok(Arc::clone(jars)),
             None => {
                 let jars = dirs::cache_dir()
                     .with_context(|| "failed to get cache directory")?
                     .join("axiom");
                 self.jars = Some(jars.into());
-                Ok(Rc::clone(self.jars.as_ref().unwrap()))
+                Ok(Arc::clone(self.jars.as_ref().unwrap()))
             }
         }
     }
 
+    /// Get the program responsible for running the current package's server in the background.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - There is a problem reading the package manifest.
+    /// - The backend selected in the manifest is not installed.
+    pub fn backend(&mut self) -> Result<Box<dyn Backend>, crate::error::Error> {
+        let package = self.package()?;
+        self.backend_for(&package)
+    }
+
+    /// Get the program responsible for running `package`'s server in the background.
+    ///
+    /// Unlike [`Self::backend`], this works for any package (e.g., every package in `list`).
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - The backend selected in the manifest is not installed.
+    pub fn backend_for(
+        &self,
+        package: &axiom::Package,
+    ) -> Result<Box<dyn Backend>, crate::error::Error> {
+        let backend = package
+            .manifest()
+            .launcher()
+            .map(|launcher| *launcher.backend())
+            .unwrap_or_default();
+
+        match backend {
+            axiom::manifest::Backend::Tmux => {
+                crate::commands::ensure_tmux_installed()?;
+                Ok(Box::new(TmuxBackend::new(self.tmux_session())))
+            }
+            axiom::manifest::Backend::Systemd => Ok(Box::new(SystemdBackend::new())),
+        }
+    }
+
     /// Get a reference to an Axiom package.
     ///
     /// A package contains the manifest (the `Axiom.toml` file) and all of its files.
@@ -43,19 +253,41 @@ impl Context {
     ///
     /// This function returns an error if:
     ///
-    /// - There is a problem getting the current directory.
+    /// - There is a problem getting the current directory (when `--dir` isn't used).
     /// - There is a problem reading and parsing the manifest file.
-    pub fn package(&mut self) -> Result<Rc<axiom::Package>, anyhow::Error> {
+    pub fn package(&mut self) -> Result<Arc<axiom::Package>, anyhow::Error> {
         match &self.package {
-            Some(package) => Ok(Rc::clone(package)),
+            Some(package) => Ok(Arc::clone(package)),
             None => {
-                let path =
-                    std::env::current_dir().with_context(|| "failed to get current directory")?;
+                let path = self.package_directory()?;
                 let manifest = axiom::Manifest::from_directory(&path)
                     .with_context(|| "failed to get package manifest")?;
-                self.package = Some(Rc::new(axiom::Package::new(path, manifest)));
-                Ok(Rc::clone(self.package.as_ref().unwrap()))
+                self.package = Some(Arc::new(axiom::Package::new(path, manifest)));
+                Ok(Arc::clone(self.package.as_ref().unwrap()))
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_versions() {
+        let versions = ["1.21.5", "1.21.6"].map(|v| axiom::paper::Version::new(v.to_owned()));
+        let mut ctx = Context::default().with_versions(versions.to_vec());
+
+        let cached = ctx.versions().unwrap();
+        assert_eq!(
+            cached.iter().map(|v| v.as_str()).collect::<Vec<_>>(),
+            ["1.21.5", "1.21.6"]
+        );
+
+        // The list can be shared with other threads without copying it.
+        let shared = Arc::clone(&cached);
+        let handle = std::thread::spawn(move || shared.len());
+        assert_eq!(handle.join().unwrap(), 2);
+        assert!(Arc::ptr_eq(&cached, &ctx.versions().unwrap()));
+    }
+}
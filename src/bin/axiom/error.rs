@@ -37,7 +37,6 @@ impl Error {
         }
     }
 
-    #[allow(dead_code)]
     pub fn with_hint<H, F>(self, hint: F) -> Self
     where
         H: std::fmt::Display + Send + Sync + 'static,
@@ -49,7 +48,6 @@ impl Error {
         }
     }
 
-    #[allow(dead_code)]
     pub fn new_with_hint<H, E>(hint: H, source: E) -> Self
     where
         H: std::fmt::Display + Send + Sync + 'static,
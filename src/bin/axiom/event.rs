@@ -0,0 +1,82 @@
+//! This module implements the events printed by `--porcelain`, which let other programs (e.g., a
+//! dashboard) follow the progress of long-running commands.
+//!
+//! Each event is printed to stdout as a single line of JSON, like `{"event":"built"}`.
+
+/// Describes a milestone reached by a command.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// Part of a server JAR has been downloaded.
+    Download {
+        /// The name of the file being downloaded (e.g., `paper-1.21.6-34.jar`), since `update
+        /// --all` downloads several at once.
+        jar: String,
+        /// How much of the file has been downloaded, from 0 to 100.
+        pct: u8,
+    },
+    /// The package was switched to a different server JAR.
+    Updated {
+        /// The Minecraft version the server is now using.
+        version: String,
+        /// The build the server is now using.
+        build: i64,
+    },
+    /// The server was built, and is ready to be started.
+    Built,
+    /// The server finished starting, and players can connect to it.
+    Online,
+    /// The server has stopped.
+    Stopped,
+    /// The command failed.
+    Error {
+        /// What went wrong.
+        message: String,
+        /// How the problem might be fixed.
+        hint: Option<String>,
+    },
+    /// The command finished successfully.
+    Done,
+}
+
+impl Event {
+    /// Print the event to stdout as a single line of JSON.
+    pub fn emit(&self) {
+        use std::io::Write;
+
+        let Ok(line) = serde_json::to_string(self) else {
+            return;
+        };
+
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{line}").ok();
+        // Consumers read events as they happen, so don't leave them sitting in a buffer.
+        stdout.flush().ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_json() {
+        let json = |event: Event| serde_json::to_string(&event).unwrap();
+
+        assert_eq!(
+            json(Event::Download {
+                jar: "paper-1.21.6-34.jar".to_owned(),
+                pct: 42
+            }),
+            r#"{"event":"download","jar":"paper-1.21.6-34.jar","pct":42}"#
+        );
+        assert_eq!(json(Event::Done), r#"{"event":"done"}"#);
+        assert_eq!(
+            json(Event::Updated {
+                version: "1.21.6".to_owned(),
+                build: 34
+            }),
+            r#"{"event":"updated","version":"1.21.6","build":34}"#
+        );
+    }
+}
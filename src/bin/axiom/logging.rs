@@ -3,11 +3,20 @@ pub struct Verbosity {
     /// Use verbose output (or `-vv` and `-vvv` for more verbose output).
     #[arg(long, short = 'v', action = clap::ArgAction::Count, global = true)]
     verbose: u8,
+
+    /// Only print warnings, errors, and results, leaving out progress and status messages.
+    #[arg(long, short = 'q', global = true, conflicts_with = "verbose")]
+    quiet: bool,
 }
 
 impl Verbosity {
     pub(crate) fn level_filter(&self) -> tracing::level_filters::LevelFilter {
         use tracing::level_filters::LevelFilter;
+
+        if self.quiet {
+            return LevelFilter::WARN;
+        }
+
         match self.verbose {
             0 => LevelFilter::WARN,
             1 => LevelFilter::INFO,
@@ -15,4 +24,9 @@ impl Verbosity {
             _ => LevelFilter::TRACE,
         }
     }
+
+    /// Check whether non-essential output should be left out (see [`crate::context::Context::quiet`]).
+    pub(crate) fn quiet(&self) -> bool {
+        self.quiet
+    }
 }
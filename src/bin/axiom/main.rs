@@ -1,23 +1,56 @@
+mod backend;
 mod commands;
 mod context;
 mod error;
+mod event;
 mod logging;
+mod registry;
+#[cfg(test)]
+mod testing;
 
 use std::io::Write;
 
+use anyhow::Context as _;
 use clap::Parser;
 use colored::Colorize;
 use tracing_subscriber::prelude::*;
 
+use crate::context::Context;
 use crate::logging::Verbosity;
 
 #[derive(clap::Parser)]
+#[command(version)]
 struct Args {
     #[command(subcommand)]
     command: commands::Subcommand,
 
     #[clap(flatten)]
     verbose: Verbosity,
+
+    /// The name of the tmux socket the servers run on.
+    ///
+    /// Servers started under a different socket are invisible to Axiom, so changing this while
+    /// servers are running hides them until it is changed back.
+    #[arg(long, global = true, env = "AXIOM_TMUX_SOCKET", value_name = "NAME")]
+    tmux_socket: Option<String>,
+
+    /// Only use server JARs that have already been downloaded, without contacting PaperMC.
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Print errors (and any structured output) as JSON, for use in scripts.
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Print the progress of long-running commands as newline-delimited JSON events on stdout,
+    /// instead of human-readable status messages. Anything else a command prints (e.g., tables)
+    /// goes to stderr.
+    #[arg(long, global = true)]
+    porcelain: bool,
+
+    /// Run as if Axiom was started in this directory, instead of the current directory.
+    #[arg(long, short = 'C', global = true, value_name = "PATH")]
+    dir: Option<std::path::PathBuf>,
 }
 
 /// Describes the result of the process after it has terminated.
@@ -37,32 +70,101 @@ impl std::process::Termination for ExitCode {
 
 /// The main entry point to the application.
 fn main() -> ExitCode {
-    try_main().unwrap_or_else(|err| {
-        let mut stderr = std::io::stderr().lock();
-        writeln!(stderr, "{}", "an error occurred".bold().red()).ok();
-
-        let mut current_error: Option<&dyn std::error::Error> = Some(&err);
+    let args = Args::parse();
+    let json = args.json;
+    let porcelain = args.porcelain;
 
-        while let Some(cause) = current_error {
-            writeln!(stderr, "  {}: {}", "Cause".bold(), cause).ok();
-            current_error = cause.source();
+    try_main(args).unwrap_or_else(|err| {
+        if porcelain {
+            crate::event::Event::Error {
+                message: err.to_string(),
+                hint: err.hint().map(str::to_owned),
+            }
+            .emit();
         }
 
-        if let Some(hint) = err.hint() {
-            writeln!(stderr, "  {}: {}", "Hint".bold().green(), hint).ok();
+        if json {
+            report_error_as_json(&err);
+        } else {
+            report_error(&err);
         }
 
         ExitCode::Failure
     })
 }
 
-fn try_main() -> Result<ExitCode, crate::error::Error> {
-    let args = Args::parse();
+fn try_main(args: Args) -> Result<ExitCode, crate::error::Error> {
     let level_filter = args.verbose.level_filter();
 
     tracing_subscriber::registry()
-        .with(tracing_subscriber::fmt::layer().with_filter(level_filter))
+        // Stdout is reserved for results, and for the JSON printed by `--json` and `--porcelain`.
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_filter(level_filter),
+        )
         .init();
 
-    args.command.run().map(|()| ExitCode::Success)
+    let directory = args
+        .dir
+        .map(|dir| match dir.is_dir() {
+            true => std::path::absolute(&dir)
+                .with_context(|| format!("failed to resolve {}", dir.display())),
+            false => Err(anyhow::anyhow!(
+                "directory {} does not exist",
+                dir.display()
+            )),
+        })
+        .transpose()?;
+
+    let mut ctx = Context::default()
+        .with_directory(directory)
+        .with_tmux_socket(args.tmux_socket)
+        .with_json(args.json)
+        .with_offline(args.offline)
+        .with_quiet(args.verbose.quiet())
+        .with_porcelain(args.porcelain);
+    args.command.run(&mut ctx)?;
+    ctx.emit(crate::event::Event::Done);
+
+    Ok(ExitCode::Success)
+}
+
+/// Print the error, each of its causes, and the hint (if any) in a human-readable format.
+fn report_error(err: &crate::error::Error) {
+    let mut stderr = std::io::stderr().lock();
+    writeln!(stderr, "{}", "an error occurred".bold().red()).ok();
+
+    let mut current_error: Option<&dyn std::error::Error> = Some(err);
+
+    while let Some(cause) = current_error {
+        writeln!(stderr, "  {}: {}", "Cause".bold(), cause).ok();
+        current_error = cause.source();
+    }
+
+    if let Some(hint) = err.hint() {
+        writeln!(stderr, "  {}: {}", "Hint".bold().green(), hint).ok();
+    }
+}
+
+/// Print the error as a single JSON object, for scripts that need to parse it.
+///
+/// The object looks like: `{ "error": "...", "causes": ["..."], "hint": "..." }`.
+fn report_error_as_json(err: &crate::error::Error) {
+    let mut causes = Vec::new();
+    let mut current_error = std::error::Error::source(err);
+
+    while let Some(cause) = current_error {
+        causes.push(cause.to_string());
+        current_error = cause.source();
+    }
+
+    let report = serde_json::json!({
+        "error": err.to_string(),
+        "causes": causes,
+        "hint": err.hint(),
+    });
+
+    let mut stderr = std::io::stderr().lock();
+    writeln!(stderr, "{report}").ok();
 }
@@ -0,0 +1,119 @@
+//! This module implements the package registry, which remembers where each package was created so
+//! that commands can work across all of them (e.g., `list` and `cache clean`).
+//!
+//! Packages can live anywhere, so `new` adds the package's absolute path to the registry and
+//! `delete` removes it again.
+
+use anyhow::Context;
+
+/// The name of the file the registry is stored in, inside of the data directory.
+const FILENAME: &str = "packages.json";
+
+/// Describes the contents of the registry file.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct Registry {
+    packages: Vec<std::path::PathBuf>,
+}
+
+impl Registry {
+    /// Read the registry from `path`, which is empty if the file does not exist yet.
+    fn load(path: &std::path::Path) -> Result<Self, anyhow::Error> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .with_context(|| format!("failed to parse {}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => {
+                Err(anyhow::Error::new(err).context(format!("failed to read {}", path.display())))
+            }
+        }
+    }
+
+    /// Write the registry to `path`, creating its parent directory if needed.
+    fn save(&self, path: &std::path::Path) -> Result<(), anyhow::Error> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, contents + "\n")
+            .with_context(|| format!("failed to write to {}", path.display()))
+    }
+
+    fn insert(&mut self, package: std::path::PathBuf) {
+        if !self.packages.contains(&package) {
+            self.packages.push(package);
+        }
+    }
+
+    fn remove(&mut self, package: &std::path::Path) {
+        self.packages.retain(|path| path != package);
+    }
+}
+
+/// Get the path to the registry file.
+fn path() -> Result<std::path::PathBuf, anyhow::Error> {
+    let data_dir = dirs::data_dir().with_context(|| "failed to get data directory")?;
+    Ok(data_dir.join("axiom").join(FILENAME))
+}
+
+/// Get the absolute paths of every registered package.
+///
+/// Packages that were moved or removed without using `delete` are skipped.
+pub fn all() -> Result<Vec<std::path::PathBuf>, anyhow::Error> {
+    let registry = Registry::load(&path()?)?;
+    let packages = registry
+        .packages
+        .into_iter()
+        .filter(|package| package.join(axiom::Manifest::FILENAME).is_file())
+        .collect();
+
+    Ok(packages)
+}
+
+/// Add the package at `package` to the registry.
+pub fn add(package: &std::path::Path) -> Result<(), anyhow::Error> {
+    let path = path()?;
+    let mut registry = Registry::load(&path)?;
+    registry.insert(std::path::absolute(package)?);
+    registry.save(&path)
+}
+
+/// Remove the package at `package` from the registry.
+pub fn remove(package: &std::path::Path) -> Result<(), anyhow::Error> {
+    let path = path()?;
+    let mut registry = Registry::load(&path)?;
+    registry.remove(&std::path::absolute(package)?);
+    registry.save(&path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("axiom").join(FILENAME);
+
+        let mut registry = Registry::load(&path).unwrap();
+        assert!(registry.packages.is_empty());
+
+        registry.insert("/srv/survival".into());
+        registry.insert("/srv/creative".into());
+        registry.insert("/srv/survival".into());
+        registry.save(&path).unwrap();
+
+        let mut registry = Registry::load(&path).unwrap();
+        assert_eq!(
+            registry.packages,
+            [
+                std::path::Path::new("/srv/survival"),
+                std::path::Path::new("/srv/creative")
+            ]
+        );
+
+        registry.remove(std::path::Path::new("/srv/survival"));
+        assert_eq!(registry.packages, [std::path::Path::new("/srv/creative")]);
+    }
+}
@@ -0,0 +1,17 @@
+//! This module contains fixtures shared by the tests of several commands.
+
+/// The manifest of the package the tests use: `example`, running Paper 1.21.6 (#34).
+pub(crate) const MANIFEST: &str = "[package]\nname = \"example\"\nversion = \"0.1.0\"\n\n\
+    [server]\nversion = \"1.21.6\"\nbuild = 34\n";
+
+/// Get the package described by [`MANIFEST`], with `extra` (e.g., more tables) added to the end
+/// of the manifest, as if it were at `path`.
+///
+/// Nothing is written to `path`.
+pub(crate) fn package(path: &std::path::Path, extra: &str) -> axiom::Package {
+    let manifest = format!("{MANIFEST}{extra}")
+        .parse::<axiom::Manifest>()
+        .expect("expected the test manifest to be valid");
+
+    axiom::Package::new(path.to_owned(), manifest)
+}
@@ -0,0 +1,176 @@
+//! This module implements functionality for checking the version of Java used to run a server.
+
+/// The minimum version of Java required to run each version of Minecraft, newest first.
+///
+/// Each entry reads as "Minecraft `version` and newer requires Java `java`". When Mojang raises
+/// the requirement again, add a new entry to the top of the list.
+const REQUIREMENTS: &[(&str, u32)] = &[("1.20.5", 21), ("1.18", 17), ("1.17", 16), ("1.12", 8)];
+
+/// Get the minimum major version of Java required to run `minecraft_version`.
+///
+/// Returns `None` if the Minecraft version is older than any version we know the requirement
+/// for.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(axiom::java::required_version("1.21.6"), Some(21));
+/// assert_eq!(axiom::java::required_version("1.20.4"), Some(17));
+/// ```
+pub fn required_version(minecraft_version: &str) -> Option<u32> {
+    let key = crate::paper::version_key(minecraft_version);
+
+    REQUIREMENTS
+        .iter()
+        .find(|(version, _)| key >= crate::paper::version_key(version))
+        .map(|(_, java)| *java)
+}
+
+/// Parse the major version of Java from the output of `java -version`.
+///
+/// The first line of the output contains the version in quotes (e.g., `openjdk version
+/// "21.0.2" 2024-01-16`). Versions before Java 9 use the `1.x` scheme (e.g., `"1.8.0_392"`).
+///
+/// # Examples
+///
+/// ```
+/// let output = r#"openjdk version "17.0.2" 2022-01-18"#;
+/// assert_eq!(axiom::java::parse_version(output), Some(17));
+/// ```
+pub fn parse_version(output: &str) -> Option<u32> {
+    let line = output.lines().find(|line| line.contains("version"))?;
+    let (_, rest) = line.split_once('"')?;
+    let (version, _) = rest.split_once('"')?;
+
+    let mut parts = version.split(['.', '_', '-', '+']);
+    let major = parts.next()?.parse().ok()?;
+
+    match major {
+        1 => parts.next()?.parse().ok(),
+        major => Some(major),
+    }
+}
+
+/// Get the major version of the Java executable at `java`.
+///
+/// # Errors
+///
+/// This function returns an error if:
+///
+/// - The command fails to execute (e.g., `java` is not installed).
+/// - We fail to parse the version from the command's output.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let version = axiom::java::installed_version(std::path::Path::new("java"))?;
+/// assert!(version >= 8);
+/// # Ok(())
+/// # }
+/// ```
+pub fn installed_version(java: &std::path::Path) -> Result<u32, JavaVersionError> {
+    let command = format!("{} -version", java.display());
+    let output = std::process::Command::new(java)
+        .arg("-version")
+        .output()
+        .map_err(|err| JavaVersionError::CommandFailed {
+            command,
+            source: err.into(),
+        })?;
+
+    // `java -version` writes to stderr, but check stdout too in case a wrapper script redirects it.
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    parse_version(&stderr)
+        .or_else(|| parse_version(&stdout))
+        .ok_or(JavaVersionError::ParseFailed)
+}
+
+/// Describes an error that occurred while getting the installed version of Java.
+#[derive(Debug)]
+pub enum JavaVersionError {
+    /// Indicates a failure to run `java -version`.
+    CommandFailed {
+        /// The command we attempted to run.
+        command: String,
+        /// The underlying error that caused the command failure.
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    /// Indicates a failure to extract the version from the command's output.
+    ParseFailed,
+}
+
+impl std::fmt::Display for JavaVersionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CommandFailed { command, source: _ } => {
+                write!(f, "failed to execute command '{}'", command)
+            }
+            Self::ParseFailed => "failed to parse Java version from command output".fmt(f),
+        }
+    }
+}
+
+impl JavaVersionError {
+    /// Check whether the error was caused by `java` not being installed.
+    pub fn is_command_not_found(&self) -> bool {
+        match self {
+            Self::CommandFailed { command: _, source } => source
+                .downcast_ref::<std::io::Error>()
+                .is_some_and(|err| err.kind() == std::io::ErrorKind::NotFound),
+            Self::ParseFailed => false,
+        }
+    }
+}
+
+impl std::error::Error for JavaVersionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::CommandFailed { command: _, source } => Some(source.as_ref()),
+            Self::ParseFailed => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_version() {
+        let input = [
+            ("1.21.6", Some(21)),
+            ("1.20.5", Some(21)),
+            ("1.20.4", Some(17)),
+            ("1.18", Some(17)),
+            ("1.17.1", Some(16)),
+            ("1.16.5", Some(8)),
+            ("1.8.8", None),
+        ];
+
+        for (version, expected) in input.into_iter() {
+            assert_eq!(required_version(version), expected, "{version}");
+        }
+    }
+
+    #[test]
+    fn test_parse_version() {
+        let input = [
+            (r#"openjdk version "21.0.2" 2024-01-16"#, Some(21)),
+            (r#"java version "1.8.0_392""#, Some(8)),
+            (r#"openjdk version "17" 2021-09-14"#, Some(17)),
+            (r#"openjdk version "22-ea" 2024-03-19"#, Some(22)),
+            (
+                "Picked up JAVA_TOOL_OPTIONS: -Xmx1G\nopenjdk version \"17.0.9\" 2023-10-17",
+                Some(17),
+            ),
+            ("command not found", None),
+        ];
+
+        for (output, expected) in input.into_iter() {
+            assert_eq!(parse_version(output), expected, "{output}");
+        }
+    }
+}
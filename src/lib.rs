@@ -19,9 +19,15 @@
 #![warn(rustdoc::missing_doc_code_examples)]
 #![doc(test(attr(deny(dead_code))))]
 
+pub mod archive;
+pub mod bedrock;
+pub mod java;
 pub mod manifest;
 pub mod package;
 pub mod paper;
+pub mod plugin;
+pub mod socks;
+pub mod tmux;
 pub mod varint;
 
 pub use manifest::{Manifest, ManifestError};
@@ -28,13 +28,31 @@ pub struct Manifest {
     server: Server,
     launcher: Option<Launcher>,
     properties: Option<Properties>,
+    plugins: Option<Plugins>,
 }
 
 impl std::str::FromStr for Manifest {
     type Err = ManifestError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        toml::from_str(s).map_err(|err| ManifestError::ParseFailed { source: err.into() })
+        let mut table = toml::from_str::<toml::Table>(s)
+            .map_err(|err| ManifestError::ParseFailed { source: err.into() })?;
+
+        // Expand environment variables only in the sections where secrets are expected to live.
+        let lookup = |name: &str| std::env::var(name).ok();
+        for section in ["server", "properties"] {
+            if let Some(value) = table.get_mut(section) {
+                interpolate_value(value, &lookup)?;
+            }
+        }
+
+        let manifest: Self = toml::Value::Table(table)
+            .try_into()
+            .map_err(|err| ManifestError::ParseFailed { source: err.into() })?;
+
+        Package::validate_name(manifest.package().name())?;
+
+        Ok(manifest)
     }
 }
 
@@ -48,12 +66,14 @@ impl Manifest {
         server: Server,
         launcher: Option<Launcher>,
         properties: Option<Properties>,
+        plugins: Option<Plugins>,
     ) -> Self {
         Self {
             package,
             server,
             launcher,
             properties,
+            plugins,
         }
     }
 
@@ -77,6 +97,11 @@ impl Manifest {
         self.properties.as_ref()
     }
 
+    /// Get the plugins that should be installed into the server's `plugins` directory.
+    pub const fn plugins(&self) -> Option<&Plugins> {
+        self.plugins.as_ref()
+    }
+
     /// Read and parse the manifest from the given base directory.
     ///
     /// This is a convenience function for joining `path` and [`Self::FILENAME`] then calling
@@ -132,6 +157,328 @@ impl Manifest {
 
         contents.parse()
     }
+
+    /// The top-level tables that may appear in a manifest.
+    const TABLES: [&'static str; 5] = ["package", "server", "launcher", "properties", "plugins"];
+
+    /// The longest time, in seconds, that the timing options in `[launcher]` may be set to.
+    const MAX_WAIT_SECS: i64 = 600;
+
+    /// Check the contents of a manifest for common mistakes, without deserializing it.
+    ///
+    /// Unlike [`str::parse`], which stops at the first problem, this reports every problem it
+    /// finds along with where it is in `s`. It checks that the required fields are present and
+    /// have the right type, that the `[launcher]` timing options are reasonable, and that there
+    /// are no unknown top-level tables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use axiom::Manifest;
+    ///
+    /// # fn main() {
+    /// let input = r#"
+    ///     [package]
+    ///     name = "example"
+    ///
+    ///     [sever]
+    ///     version = "1.21.6"
+    ///     build = 34
+    /// "#;
+    /// let errors = Manifest::validate(input).unwrap_err();
+    /// assert_eq!(errors.len(), 3);
+    /// # }
+    /// ```
+    pub fn validate(s: &str) -> Result<(), Vec<ValidationError>> {
+        let document = match toml_edit::ImDocument::parse(s) {
+            Ok(document) => document,
+            Err(err) => {
+                let span = err.span().unwrap_or_default();
+                let message = err.message().trim_end().to_owned();
+                return Err(vec![ValidationError::new(s, span, message, None)]);
+            }
+        };
+
+        let root = document.as_table();
+        let mut errors = Vec::new();
+
+        let mut missing_tables = Vec::new();
+
+        let is_str: fn(&toml_edit::Item) -> bool = toml_edit::Item::is_str;
+        let is_integer: fn(&toml_edit::Item) -> bool = toml_edit::Item::is_integer;
+
+        let required = [
+            ("package", "name", is_str, "a string"),
+            ("package", "version", is_str, "a string"),
+            ("server", "version", is_str, "a string"),
+            ("server", "build", is_integer, "an integer"),
+        ];
+
+        for (table_name, field, is_expected_type, expected) in required {
+            let Some((table_key, table)) = root.get_key_value(table_name) else {
+                // Only report a missing table once, rather than once per missing field.
+                if !missing_tables.contains(&table_name) {
+                    missing_tables.push(table_name);
+                    errors.push(ValidationError::new(
+                        s,
+                        0..0,
+                        format!("missing required table `[{table_name}]`"),
+                        None,
+                    ));
+                }
+                continue;
+            };
+
+            let Some(table) = table.as_table_like() else {
+                errors.push(ValidationError::new(
+                    s,
+                    table_key.span().unwrap_or_default(),
+                    format!("`{table_name}` should be a table"),
+                    None,
+                ));
+                continue;
+            };
+
+            let Some((_, value)) = table.get_key_value(field) else {
+                errors.push(ValidationError::new(
+                    s,
+                    table_key.span().unwrap_or_default(),
+                    format!("missing required field `{table_name}.{field}`"),
+                    Some(format!("add `{field} = ...` to the `[{table_name}]` table")),
+                ));
+                continue;
+            };
+
+            if !is_expected_type(value) {
+                errors.push(ValidationError::new(
+                    s,
+                    value.span().unwrap_or_default(),
+                    format!("`{table_name}.{field}` should be {expected}"),
+                    None,
+                ));
+            }
+        }
+
+        // Waiting for no time at all (or for hours) is almost certainly a mistake.
+        let launcher = root.get("launcher").and_then(|table| table.as_table_like());
+
+        for field in ["startup_grace_secs", "poll_interval_secs"] {
+            let Some(value) = launcher.and_then(|table| table.get(field)) else {
+                continue;
+            };
+
+            if !value
+                .as_integer()
+                .is_some_and(|secs| (1..=Self::MAX_WAIT_SECS).contains(&secs))
+            {
+                errors.push(ValidationError::new(
+                    s,
+                    value.span().unwrap_or_default(),
+                    format!(
+                        "`launcher.{field}` should be a number of seconds from 1 to {}",
+                        Self::MAX_WAIT_SECS
+                    ),
+                    None,
+                ));
+            }
+        }
+
+        for (key, _) in root.iter() {
+            if Self::TABLES.contains(&key) {
+                continue;
+            }
+
+            let span = root.key(key).and_then(|key| key.span()).unwrap_or_default();
+            let suggestion =
+                closest_match(key, &Self::TABLES).map(|table| format!("did you mean `{table}`?"));
+
+            errors.push(ValidationError::new(
+                s,
+                span,
+                format!("unknown table `{key}`"),
+                suggestion,
+            ));
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+}
+
+/// Replace every `${NAME}` in the strings inside `value` with `lookup(NAME)`.
+fn interpolate_value(
+    value: &mut toml::Value,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<(), ManifestError> {
+    match value {
+        toml::Value::String(s) => *s = interpolate(s, lookup)?,
+        toml::Value::Array(array) => array
+            .iter_mut()
+            .try_for_each(|value| interpolate_value(value, lookup))?,
+        toml::Value::Table(table) => table
+            .iter_mut()
+            .try_for_each(|(_, value)| interpolate_value(value, lookup))?,
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Replace every `${NAME}` in `s` with `lookup(NAME)`, which is usually the value of the
+/// environment variable `NAME`.
+///
+/// Use `$${...}` to write a literal `${...}`.
+fn interpolate(s: &str, lookup: &impl Fn(&str) -> Option<String>) -> Result<String, ManifestError> {
+    let mut output = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find("${") {
+        // An escaped `$${` becomes a literal `${`.
+        if rest[..start].ends_with('$') {
+            output.push_str(&rest[..start - 1]);
+            output.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+
+        let Some(length) = rest[start + 2..].find('}') else {
+            // Without a closing brace there is nothing to expand.
+            break;
+        };
+
+        let name = &rest[start + 2..start + 2 + length];
+        let value = lookup(name).ok_or_else(|| ManifestError::UndefinedVariable {
+            name: name.to_owned(),
+        })?;
+
+        output.push_str(&rest[..start]);
+        output.push_str(&value);
+        rest = &rest[start + 2 + length + 1..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Find the candidate that is most likely to be what the user meant to type instead of `input`.
+fn closest_match<'a>(input: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    fn distance(a: &str, b: &str) -> usize {
+        let b = b.chars().collect::<Vec<_>>();
+        let mut previous = (0..=b.len()).collect::<Vec<_>>();
+
+        for (i, ca) in a.chars().enumerate() {
+            let mut current = vec![i + 1];
+
+            for (j, cb) in b.iter().enumerate() {
+                let substitution = previous[j] + usize::from(ca != *cb);
+                current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+            }
+
+            previous = current;
+        }
+
+        previous[b.len()]
+    }
+
+    candidates
+        .iter()
+        .map(|&candidate| (candidate, distance(input, candidate)))
+        .filter(|&(_, distance)| distance <= 2)
+        .min_by_key(|&(_, distance)| distance)
+        .map(|(candidate, _)| candidate)
+}
+
+/// Describes a single problem found by [`Manifest::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    line: usize,
+    column: usize,
+    message: String,
+    suggestion: Option<String>,
+}
+
+impl ValidationError {
+    fn new(
+        input: &str,
+        span: std::ops::Range<usize>,
+        message: String,
+        suggestion: Option<String>,
+    ) -> Self {
+        let before = &input[..span.start.min(input.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count()
+            + 1;
+
+        Self {
+            line,
+            column,
+            message,
+            suggestion,
+        }
+    }
+
+    /// Get the line where the problem was found, starting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get the column where the problem was found, starting from 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Get a description of the problem.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get a suggestion for how to fix the problem, if there is one.
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " ({suggestion})")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Find the manifest file in `start` or the closest of its parent directories, like Cargo does
+/// for `Cargo.toml`.
+///
+/// Returns the path to the manifest file, or `None` if the filesystem root was reached without
+/// finding one.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let start = std::env::current_dir()?.join("server").join("plugins");
+/// let manifest_path = axiom::manifest::find_manifest_upward(&start);
+/// # Ok(())
+/// # }
+/// ```
+pub fn find_manifest_upward(start: &std::path::Path) -> Option<std::path::PathBuf> {
+    start
+        .ancestors()
+        .map(|directory| directory.join(Manifest::FILENAME))
+        .find(|path| path.is_file())
 }
 
 /// Describes an error that occurred while attempting to parse a manifest.
@@ -152,6 +499,26 @@ pub enum ManifestError {
         /// The underlying error that caused the failure.
         source: Box<dyn std::error::Error + Send + Sync + 'static>,
     },
+    /// Indicates the manifest refers to an environment variable (e.g., `${NAME}`) that is not
+    /// set.
+    UndefinedVariable {
+        /// The name of the environment variable.
+        name: String,
+    },
+    /// Indicates the package name cannot be used (see [`Package::validate_name`]).
+    InvalidName {
+        /// The package name that was rejected.
+        name: String,
+        /// A description of the rule the package name violated.
+        rule: &'static str,
+    },
+    /// Indicates a property's value can't be written to `server.properties` (e.g., an array).
+    UnsupportedProperty {
+        /// The full key of the property (e.g., `query.port`).
+        key: String,
+        /// The type of the value that was rejected.
+        kind: &'static str,
+    },
 }
 
 impl std::fmt::Display for ManifestError {
@@ -166,6 +533,16 @@ impl std::fmt::Display for ManifestError {
             }
             Self::ReadFailed { source: _ } => "failed to read manifest file".fmt(f),
             Self::ParseFailed { source: _ } => "failed to parse manifest".fmt(f),
+            Self::UndefinedVariable { name } => {
+                write!(f, "environment variable '{name}' is not set")
+            }
+            Self::InvalidName { name, rule } => {
+                write!(f, "invalid package name '{name}': {rule}")
+            }
+            Self::UnsupportedProperty { key, kind } => write!(
+                f,
+                "property '{key}' cannot be {kind}; only strings, numbers, and booleans are supported"
+            ),
         }
     }
 }
@@ -176,6 +553,9 @@ impl std::error::Error for ManifestError {
             Self::NotFound { path: _ } => None,
             Self::ReadFailed { source } => Some(source.as_ref()),
             Self::ParseFailed { source } => Some(source.as_ref()),
+            Self::UndefinedVariable { name: _ } => None,
+            Self::InvalidName { name: _, rule: _ } => None,
+            Self::UnsupportedProperty { key: _, kind: _ } => None,
         }
     }
 }
@@ -215,8 +595,43 @@ impl Package {
     /// Package names should be alphanumeric and may contain dashes and underscores.
     /// Package names should not contain any colons (`:`) or periods (`.`).
     pub fn valid_name(name: &str) -> bool {
-        name.chars()
+        Self::validate_name(name).is_ok()
+    }
+
+    /// Like [`Self::valid_name`], but returns an error describing which rule `name` violated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use axiom::manifest::Package;
+    ///
+    /// # fn main() {
+    /// assert!(Package::validate_name("survival").is_ok());
+    /// assert!(Package::validate_name("1.21.6").is_err());
+    /// # }
+    /// ```
+    pub fn validate_name(name: &str) -> Result<(), ManifestError> {
+        let rule = if name.is_empty() {
+            "package names must not be empty"
+        } else if name.contains(':') {
+            "package names must not contain colons (`:`)"
+        } else if name.contains('.') {
+            "package names must not contain periods (`.`)"
+        } else if name.contains(char::is_whitespace) {
+            "package names must not contain whitespace"
+        } else if !name
+            .chars()
             .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            "package names may only contain letters, numbers, dashes, and underscores"
+        } else {
+            return Ok(());
+        };
+
+        Err(ManifestError::InvalidName {
+            name: name.to_owned(),
+            rule,
+        })
     }
 
     /// Get the name of the package.
@@ -235,6 +650,8 @@ impl Package {
 pub struct Server {
     version: String,
     build: i64, // The `toml` crate uses `i64` for its integer value.
+    pre_build: Option<std::path::PathBuf>,
+    post_build: Option<std::path::PathBuf>,
 }
 
 impl Server {
@@ -246,11 +663,22 @@ impl Server {
     /// # fn main() {
     /// let version = "1.21.6".to_owned();
     /// let build = 34;
-    /// let server = Server::new(version, build);
+    /// let post_build = "./scripts/install-plugins.sh".into();
+    /// let server = Server::new(version, build, None, Some(post_build));
     /// # }
     /// ```
-    pub fn new(version: String, build: i64) -> Self {
-        Self { version, build }
+    pub fn new(
+        version: String,
+        build: i64,
+        pre_build: Option<std::path::PathBuf>,
+        post_build: Option<std::path::PathBuf>,
+    ) -> Self {
+        Self {
+            version,
+            build,
+            pre_build,
+            post_build,
+        }
     }
 
     /// Get the Minecraft server version.
@@ -262,6 +690,18 @@ impl Server {
     pub fn build(&self) -> i64 {
         self.build
     }
+
+    /// Get the path to the script to run before building the server, relative to the package
+    /// directory.
+    pub fn pre_build(&self) -> Option<&std::path::Path> {
+        self.pre_build.as_deref()
+    }
+
+    /// Get the path to the script to run after building the server, relative to the package
+    /// directory.
+    pub fn post_build(&self) -> Option<&std::path::Path> {
+        self.post_build.as_deref()
+    }
 }
 
 /// Contains information related to the generation of the `start.sh` script.
@@ -271,6 +711,11 @@ pub struct Launcher {
     memory: Option<String>,
     jvm_args: Option<Vec<String>>,
     game_args: Option<Vec<String>>,
+    #[serde(default)]
+    backend: Backend,
+    java: Option<std::path::PathBuf>,
+    startup_grace_secs: Option<u64>,
+    poll_interval_secs: Option<u64>,
 }
 
 impl Launcher {
@@ -279,14 +724,16 @@ impl Launcher {
     /// # Examples
     ///
     /// ```
-    /// use axiom::manifest::{Launcher, Preset};
+    /// use axiom::manifest::{Backend, Launcher, Preset};
     ///
     /// # fn main() {
     /// let preset = Preset::None;
     /// let memory = "4G".to_owned();
     /// let jvm_args = vec!["-XX:+UseG1GC".to_owned()];
     /// // let game_args = vec![];
-    /// let launcher = Launcher::new(preset, Some(memory), Some(jvm_args), None);
+    /// let backend = Backend::Tmux;
+    /// let java = "/usr/lib/jvm/java-21-openjdk/bin/java".into();
+    /// let launcher = Launcher::new(preset, Some(memory), Some(jvm_args), None, backend, Some(java));
     /// # }
     /// ```
     pub fn new(
@@ -294,12 +741,18 @@ impl Launcher {
         memory: Option<String>,
         jvm_args: Option<Vec<String>>,
         game_args: Option<Vec<String>>,
+        backend: Backend,
+        java: Option<std::path::PathBuf>,
     ) -> Self {
         Self {
             preset,
             memory,
             jvm_args,
             game_args,
+            backend,
+            java,
+            startup_grace_secs: None,
+            poll_interval_secs: None,
         }
     }
 
@@ -341,6 +794,46 @@ impl Launcher {
     pub fn game_args(&self) -> Option<&[String]> {
         self.game_args.as_deref()
     }
+
+    /// Get the program responsible for running the server in the background.
+    pub const fn backend(&self) -> &Backend {
+        &self.backend
+    }
+
+    /// Get the path to the `java` executable that should run the server.
+    ///
+    /// This is useful if multiple versions of Java are installed. Relative paths are relative to
+    /// the package directory. If unset, `java` is looked up in `PATH`.
+    pub fn java(&self) -> Option<&std::path::Path> {
+        self.java.as_deref()
+    }
+
+    /// Get how many seconds to wait after launching the server before looking for its
+    /// `latest.log`.
+    ///
+    /// If unset, the default chosen by Axiom is used.
+    pub const fn startup_grace_secs(&self) -> Option<u64> {
+        self.startup_grace_secs
+    }
+
+    /// Get how many seconds to wait between each check of the server's `latest.log` while it is
+    /// starting or stopping.
+    ///
+    /// If unset, the default chosen by Axiom is used.
+    pub const fn poll_interval_secs(&self) -> Option<u64> {
+        self.poll_interval_secs
+    }
+}
+
+/// The program responsible for running the server in the background.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    /// Run the server in a tmux window, which allows attaching to the server console.
+    #[default]
+    Tmux,
+    /// Run the server as a `systemd --user` service, for headless machines without tmux.
+    Systemd,
 }
 
 /// Preset command-line flags for the JVM (Java Virtual Machine) to enhance server performance.
@@ -423,7 +916,7 @@ impl Properties {
     ///
     /// // NOTE: The entries are sorted in alphabetical order.
     /// let expected = "motd=A Minecraft server\npvp=true".to_owned();
-    /// assert_eq!(properties.to_server_properties(), expected);
+    /// assert_eq!(properties.to_server_properties().unwrap(), expected);
     /// # }
     /// ```
     pub fn new(items: std::collections::BTreeMap<String, toml::Value>) -> Self {
@@ -436,30 +929,535 @@ impl Properties {
     }
 
     /// Serialize the TOML properties into the format expected by the `server.properties` file.
-    pub fn to_server_properties(&self) -> String {
-        fn serialize_item(key: &str, value: &toml::Value, prefix: Option<String>) -> String {
-            let prefix = prefix.unwrap_or_default();
-
-            match value {
-                toml::Value::String(v) => format!("{}{}={}", prefix, key, v.replace(":", "\\:")),
-                toml::Value::Integer(v) => format!("{}{}={}", prefix, key, v),
-                toml::Value::Float(v) => format!("{}{}={}", prefix, key, v),
-                toml::Value::Boolean(v) => format!("{}{}={}", prefix, key, v),
-                toml::Value::Datetime(_) => unimplemented!("datetime not supported"),
-                toml::Value::Array(_) => unimplemented!("array not supported"),
-                toml::Value::Table(v) => v
-                    .iter()
-                    .map(|(k, v)| serialize_item(k, v, Some(format!("{}{}.", prefix, key))))
-                    .collect::<Vec<String>>()
-                    .join("\n"),
+    ///
+    /// Nested tables are flattened into dotted keys (e.g., `[properties.query]` with `port`
+    /// becomes `query.port`).
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - A value is an array or a datetime, which `server.properties` has no way to represent.
+    pub fn to_server_properties(&self) -> Result<String, ManifestError> {
+        let lines = self
+            .server_properties_lines()?
+            .into_iter()
+            .map(|(_, line)| line)
+            .collect::<Vec<_>>();
+
+        Ok(lines.join("\n"))
+    }
+
+    /// Parse the contents of a `server.properties` file.
+    ///
+    /// Every value is read as a string, since `server.properties` doesn't keep track of types.
+    /// Keys are kept as they are (e.g., `query.port` is not turned into a nested table).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use axiom::manifest::Properties;
+    ///
+    /// # fn main() {
+    /// let properties = Properties::from_server_properties("#Comment\nmotd=Hello\\: world\n");
+    /// let motd = properties.items().get("motd").and_then(|value| value.as_str());
+    /// assert_eq!(motd, Some("Hello: world"));
+    /// # }
+    /// ```
+    pub fn from_server_properties(s: &str) -> Self {
+        let items = s
+            .lines()
+            .filter_map(parse_server_properties_line)
+            .map(|(key, value)| (key, toml::Value::String(value)))
+            .collect();
+
+        Self { items }
+    }
+
+    /// Update the contents of an existing `server.properties` file with these properties.
+    ///
+    /// Only the keys set in the manifest are changed; every other line (including comments and
+    /// keys generated by the server) is kept as-is. Keys that aren't in `existing` yet are added
+    /// to the end.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error for the same reasons as [`Self::to_server_properties`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use axiom::manifest::Properties;
+    ///
+    /// # fn main() {
+    /// let mut items = std::collections::BTreeMap::<String, toml::Value>::new();
+    /// items.insert("pvp".to_owned(), toml::Value::Boolean(false));
+    /// let properties = Properties::new(items);
+    ///
+    /// let existing = "#Minecraft server properties\npvp=true\nserver-port=25565\n";
+    /// let expected = "#Minecraft server properties\npvp=false\nserver-port=25565\n";
+    /// assert_eq!(properties.merge_into_server_properties(existing).unwrap(), expected);
+    /// # }
+    /// ```
+    pub fn merge_into_server_properties(&self, existing: &str) -> Result<String, ManifestError> {
+        let mut pending = self
+            .server_properties_lines()?
+            .into_iter()
+            .collect::<std::collections::BTreeMap<_, _>>();
+        let keys = pending
+            .keys()
+            .cloned()
+            .collect::<std::collections::BTreeSet<_>>();
+        let mut lines = Vec::new();
+
+        for line in existing.lines() {
+            match parse_server_properties_line(line).map(|(key, _)| key) {
+                // Only the first occurrence of a key is replaced. The server uses the last
+                // occurrence, so drop any duplicates that come after it.
+                Some(key) if pending.contains_key(&key) => {
+                    lines.push(pending.remove(&key).unwrap())
+                }
+                Some(key) if keys.contains(&key) => {}
+                _ => lines.push(line.to_owned()),
+            }
+        }
+
+        lines.extend(pending.into_values());
+
+        let mut contents = lines.join("\n");
+        contents.push('\n');
+        Ok(contents)
+    }
+
+    /// Serialize each property into a `key=value` line, keeping track of the (dotted) key.
+    fn server_properties_lines(&self) -> Result<Vec<(String, String)>, ManifestError> {
+        fn serialize_item(
+            key: &str,
+            value: &toml::Value,
+            lines: &mut Vec<(String, String)>,
+        ) -> Result<(), ManifestError> {
+            let unsupported = |kind| ManifestError::UnsupportedProperty {
+                key: key.to_owned(),
+                kind,
+            };
+
+            let line = match value {
+                toml::Value::String(v) => format!("{}={}", key, v.replace(":", "\\:")),
+                toml::Value::Integer(v) => format!("{}={}", key, v),
+                toml::Value::Float(v) => format!("{}={}", key, v),
+                toml::Value::Boolean(v) => format!("{}={}", key, v),
+                toml::Value::Datetime(_) => return Err(unsupported("a datetime")),
+                toml::Value::Array(_) => return Err(unsupported("an array")),
+                toml::Value::Table(v) => {
+                    for (k, v) in v.iter() {
+                        serialize_item(&format!("{key}.{k}"), v, lines)?;
+                    }
+
+                    return Ok(());
+                }
+            };
+
+            lines.push((key.to_owned(), line));
+            Ok(())
+        }
+
+        let mut lines = Vec::new();
+
+        for (key, value) in self.items.iter() {
+            serialize_item(key, value, &mut lines)?;
+        }
+
+        Ok(lines)
+    }
+}
+
+/// Parse a single line of a `server.properties` file into its key and value.
+///
+/// `server.properties` uses the Java properties format: comments start with `#` or `!`, the key
+/// ends at the first unescaped `=`, `:`, or whitespace, and backslashes escape the next character
+/// (including `\uXXXX` for Unicode). Returns `None` for blank lines and comments.
+fn parse_server_properties_line(line: &str) -> Option<(String, String)> {
+    let line = line.trim_start();
+
+    if line.is_empty() || line.starts_with(['#', '!']) {
+        return None;
+    }
+
+    // Find the end of the key, skipping over escaped characters.
+    let mut end = line.len();
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '=' | ':' => {
+                end = i;
+                break;
+            }
+            c if c.is_whitespace() => {
+                end = i;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let (key, rest) = line.split_at(end);
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix(['=', ':']).unwrap_or(rest).trim_start();
+
+    Some((
+        unescape_server_property(key),
+        unescape_server_property(rest),
+    ))
+}
+
+/// Undo the escaping used by the Java properties format (e.g., `\:` becomes `:`).
+fn unescape_server_property(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('t') => result.push('\t'),
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('f') => result.push('\u{c}'),
+            Some('u') => {
+                let hex = chars.by_ref().take(4).collect::<String>();
+                let c = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                result.extend(c);
+            }
+            Some(c) => result.push(c),
+            None => {}
+        }
+    }
+
+    result
+}
+
+/// Contains the plugins that will be installed into the server's `plugins` directory.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() {
+/// let input = r#"
+///     [package]
+///     name = "example"
+///     version = "0.1.0"
+///
+///     [server]
+///     version = "1.21.6"
+///     build = 34
+///
+///     [plugins]
+///     example = "https://example.com/Example-1.0.0.jar"
+///     luckperms = { modrinth = "luckperms", version = "v5.5.0-bukkit" }
+/// "#;
+/// let manifest = input.parse::<axiom::Manifest>().unwrap();
+/// assert_eq!(manifest.plugins().unwrap().items().len(), 2);
+/// # }
+/// ```
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Plugins {
+    #[serde(flatten)]
+    items: std::collections::BTreeMap<String, PluginSource>,
+}
+
+impl Plugins {
+    /// Construct a new "plugins" section for the manifest.
+    pub fn new(items: std::collections::BTreeMap<String, PluginSource>) -> Self {
+        Self { items }
+    }
+
+    /// Get the plugins, keyed by a name used to refer to them.
+    pub fn items(&self) -> &std::collections::BTreeMap<String, PluginSource> {
+        &self.items
+    }
+}
+
+/// Describes where a plugin should be downloaded from.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum PluginSource {
+    /// A direct link to the plugin's JAR file.
+    Url(String),
+    /// A project on [Modrinth](https://modrinth.com).
+    Modrinth {
+        /// The project's ID or slug.
+        modrinth: String,
+        /// The version of the project to install.
+        version: String,
+    },
+    /// A project on [Hangar](https://hangar.papermc.io).
+    Hangar {
+        /// The project's slug.
+        hangar: String,
+        /// The version of the project to install.
+        version: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest_with_name(name: &str) -> String {
+        format!(
+            r#"
+                [package]
+                name = "{name}"
+                version = "0.1.0"
+
+                [server]
+                version = "1.21.6"
+                build = 34
+            "#
+        )
+    }
+
+    #[test]
+    fn test_valid_name() {
+        for name in ["survival", "creative_2", "mini-games", "Lobby"] {
+            assert!(
+                manifest_with_name(name).parse::<Manifest>().is_ok(),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        // Several mistakes at once: a missing field, a mistyped field, a misspelled table, and
+        // an unknown table.
+        let input = r#"[package]
+name = "example"
+
+[server]
+version = "1.21.6"
+build = "34"
+
+[propertes]
+motd = "A Minecraft server"
+
+[mods]
+"#;
+        let errors = Manifest::validate(input).unwrap_err();
+        let actual = errors
+            .iter()
+            .map(|error| {
+                (
+                    error.line(),
+                    error.column(),
+                    error.message(),
+                    error.suggestion(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let expected = vec![
+            (
+                1,
+                2,
+                "missing required field `package.version`",
+                Some("add `version = ...` to the `[package]` table"),
+            ),
+            (6, 9, "`server.build` should be an integer", None),
+            (
+                8,
+                2,
+                "unknown table `propertes`",
+                Some("did you mean `properties`?"),
+            ),
+            (11, 2, "unknown table `mods`", None),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_validate_launcher_timing() {
+        let input = manifest_with_name("example")
+            + "\n[launcher]\npreset = \"none\"\nstartup_grace_secs = 2\npoll_interval_secs = 0\n";
+        let errors = Manifest::validate(&input).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message(),
+            "`launcher.poll_interval_secs` should be a number of seconds from 1 to 600"
+        );
+
+        let manifest = input.replace("= 0", "= 1").parse::<Manifest>().unwrap();
+        let launcher = manifest.launcher().unwrap();
+        assert_eq!(launcher.startup_grace_secs(), Some(2));
+        assert_eq!(launcher.poll_interval_secs(), Some(1));
+    }
+
+    #[test]
+    fn test_validate_missing_table() {
+        let errors =
+            Manifest::validate("[package]\nname = \"a\"\nversion = \"0.1.0\"\n").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message(), "missing required table `[server]`");
+        assert!(Manifest::validate(&manifest_with_name("example")).is_ok());
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let variables = std::collections::HashMap::from([("RCON_PASSWORD", "hunter2")]);
+        let lookup = |name: &str| variables.get(name).map(|value| value.to_string());
+
+        assert_eq!(interpolate("${RCON_PASSWORD}", &lookup).unwrap(), "hunter2");
+        assert_eq!(
+            interpolate("Costs $${PRICE}", &lookup).unwrap(),
+            "Costs ${PRICE}"
+        );
+        assert_eq!(interpolate("${unclosed", &lookup).unwrap(), "${unclosed");
+
+        let mut value = toml::Value::Table(toml::toml! {
+            motd = "Costs $${PRICE}"
+            rcon.password = "${RCON_PASSWORD}"
+            ops = ["${RCON_PASSWORD}"]
+        });
+        interpolate_value(&mut value, &lookup).unwrap();
+
+        assert_eq!(value["motd"].as_str(), Some("Costs ${PRICE}"));
+        assert_eq!(value["rcon"]["password"].as_str(), Some("hunter2"));
+        assert_eq!(value["ops"][0].as_str(), Some("hunter2"));
+    }
+
+    #[test]
+    fn test_interpolate_undefined() {
+        match interpolate("${RCON_PASSWORD}", &|_| None) {
+            Err(ManifestError::UndefinedVariable { name }) => assert_eq!(name, "RCON_PASSWORD"),
+            other => panic!("expected an undefined variable error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_invalid_name() {
+        let cases = [
+            ("lobby:1", "package names must not contain colons (`:`)"),
+            ("1.21.6", "package names must not contain periods (`.`)"),
+            ("my server", "package names must not contain whitespace"),
+            ("", "package names must not be empty"),
+        ];
+
+        for (name, expected_rule) in cases {
+            match manifest_with_name(name).parse::<Manifest>() {
+                Err(ManifestError::InvalidName { name: actual, rule }) => {
+                    assert_eq!(actual, name);
+                    assert_eq!(rule, expected_rule);
+                }
+                other => panic!("expected {name:?} to be rejected, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_server_properties() {
+        let input = manifest_with_name("example")
+            + "[properties]\nmotd = \"Hello: world\"\nmax-players = 20\npvp = false\n\
+                [properties.query]\nport = 25565\n";
+        let manifest = input.parse::<Manifest>().unwrap();
+        let properties = manifest.properties().unwrap();
+
+        let expected = "max-players=20\nmotd=Hello\\: world\npvp=false\nquery.port=25565";
+        assert_eq!(properties.to_server_properties().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_to_server_properties_unsupported() {
+        let query = toml::Table::from_iter([("ports".to_owned(), vec![25565].into())]);
+        let started = toml::Value::Datetime("1979-05-27".parse().unwrap());
+        let cases = [
+            ("query", query.into(), "query.ports", "an array"),
+            ("started", started, "started", "a datetime"),
+        ];
+
+        for (key, value, expected_key, expected_kind) in cases {
+            let properties = Properties::new([(key.to_owned(), value)].into());
+
+            match properties.to_server_properties() {
+                Err(ManifestError::UnsupportedProperty { key, kind }) => {
+                    assert_eq!(key, expected_key);
+                    assert_eq!(kind, expected_kind);
+                }
+                other => panic!("expected {expected_key} to be rejected, got {other:?}"),
             }
         }
+    }
+
+    #[test]
+    fn test_from_server_properties() {
+        let input = "#Minecraft server properties\n\
+            #Thu Jan 01 00:00:00 UTC 2025\n\
+            motd=A Minecraft Server\\: Survival\n\
+            ! another comment\n\
+            \n\
+            query.port = 25565\n\
+            level-name:world\n\
+            rcon.password=\n\
+            greeting=\\u00A7aHello\n";
+        let properties = Properties::from_server_properties(input);
 
-        toml::Table::try_from(self)
-            .expect("expected properties to be a valid TOML table")
+        let expected = [
+            ("greeting", "\u{a7}aHello"),
+            ("level-name", "world"),
+            ("motd", "A Minecraft Server: Survival"),
+            ("query.port", "25565"),
+            ("rcon.password", ""),
+        ];
+        let actual = properties
+            .items()
             .iter()
-            .map(|(k, v)| serialize_item(k, v, None))
-            .collect::<Vec<String>>()
-            .join("\n")
+            .map(|(key, value)| (key.as_str(), value.as_str().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_merge_into_server_properties() {
+        let input = manifest_with_name("example")
+            + "[properties]\nmotd = \"Hello\"\nmax-players = 10\n[properties.query]\nport = 25566\n";
+        let manifest = input.parse::<Manifest>().unwrap();
+        let properties = manifest.properties().unwrap();
+
+        let existing = "#Minecraft server properties\n\
+            motd=A Minecraft Server\n\
+            query.port=25565\n\
+            server-port=25565\n\
+            motd=duplicate\n";
+        let expected = "#Minecraft server properties\n\
+            motd=Hello\n\
+            query.port=25566\n\
+            server-port=25565\n\
+            max-players=10\n";
+        assert_eq!(
+            properties.merge_into_server_properties(existing).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_find_manifest_upward() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        let nested = root.join("server").join("plugins");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(root.join(Manifest::FILENAME), manifest_with_name("example")).unwrap();
+
+        assert_eq!(
+            find_manifest_upward(&nested),
+            Some(root.join(Manifest::FILENAME))
+        );
+        let not_found = find_manifest_upward(&std::path::Path::new("/").join("nonexistent"));
+        assert_eq!(not_found, None);
     }
 }
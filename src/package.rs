@@ -8,6 +8,7 @@ pub struct Package {
     path: std::path::PathBuf,
     manifest: crate::Manifest,
     manifest_path: std::path::PathBuf,
+    lockfile_path: std::path::PathBuf,
     server: Server,
 }
 
@@ -20,14 +21,20 @@ impl Package {
     /// ```
     pub fn new(path: std::path::PathBuf, manifest: crate::Manifest) -> Self {
         let manifest_path = path.join(crate::Manifest::FILENAME);
+        let lockfile_path = path.join(crate::plugin::LOCKFILE_NAME);
         let server_path = path.join("server");
         let server_jar_path = server_path.join("server.jar");
-        let server = Server::new(server_path, server_jar_path);
+        let mut server = Server::new(server_path, server_jar_path);
+
+        if let Some(java) = manifest.launcher().and_then(|launcher| launcher.java()) {
+            server = server.with_java(path.join(java));
+        }
 
         Self {
             path,
             manifest,
             manifest_path,
+            lockfile_path,
             server,
         }
     }
@@ -42,6 +49,11 @@ impl Package {
         &self.manifest_path
     }
 
+    /// Get the path to the lockfile, which records the files installed for each plugin.
+    pub fn lockfile_path(&self) -> &std::path::Path {
+        &self.lockfile_path
+    }
+
     /// Get a reference to the contents of the manifest file.
     pub fn manifest(&self) -> &crate::Manifest {
         &self.manifest
@@ -73,7 +85,10 @@ pub struct Server {
     server_properties: std::path::PathBuf,
     eula_txt: std::path::PathBuf,
     start_sh: std::path::PathBuf,
+    start_bat: std::path::PathBuf,
     logs: std::path::PathBuf,
+    plugins: std::path::PathBuf,
+    java: std::path::PathBuf,
 }
 
 impl Server {
@@ -82,7 +97,9 @@ impl Server {
         let server_properties = path.join("server.properties");
         let eula_txt = path.join("eula.txt");
         let start_sh = path.join("start.sh");
+        let start_bat = path.join("start.bat");
         let logs = path.join("logs");
+        let plugins = path.join("plugins");
 
         Self {
             path,
@@ -90,10 +107,23 @@ impl Server {
             server_properties,
             eula_txt,
             start_sh,
+            start_bat,
             logs,
+            plugins,
+            java: "java".into(),
         }
     }
 
+    /// Use a specific `java` executable instead of looking it up in `PATH`.
+    pub fn with_java(self, java: std::path::PathBuf) -> Self {
+        Self { java, ..self }
+    }
+
+    /// Get the `java` executable used to run the server JAR.
+    pub fn java(&self) -> &std::path::Path {
+        &self.java
+    }
+
     /// Get the path to the directory containing the Minecraft server and its configuration files.
     pub fn path(&self) -> &std::path::Path {
         &self.path
@@ -129,27 +159,36 @@ impl Server {
         &self.start_sh
     }
 
+    /// Get the path to the server's `start.bat` file.
+    ///
+    /// The `start.bat` file is the equivalent of `start.sh` on Windows.
+    pub fn start_bat(&self) -> &std::path::Path {
+        &self.start_bat
+    }
+
     /// Get the path to the server's `logs` directory.
     pub fn logs(&self) -> &std::path::Path {
         &self.logs
     }
 
+    /// Get the path to the server's `plugins` directory.
+    pub fn plugins(&self) -> &std::path::Path {
+        &self.plugins
+    }
+
     /// Get the version of Minecraft the current `server.jar` is running.
     ///
     /// This function queries the `server.jar` directly to ensure we get accurate version
     /// information. Because we are creating a subprocess and running the JAR directly, this
     /// operation is relatively slow (and even slower if it's the first time running the JAR).
     ///
-    /// # Panics
-    ///
-    /// This function panics if the path to the `server.jar` file contains invalid unicode.
-    ///
     /// # Errors
     ///
     /// This function returns an error if:
     ///
     /// - The `server.jar` file does not exist.
-    /// - The command to run the `server.jar` file fails to execute.
+    /// - The command to run the `server.jar` file fails to execute (e.g., `java` is not
+    ///   installed).
     /// - We fail to parse the required information from the command's output.
     ///
     /// # Examples
@@ -160,48 +199,41 @@ impl Server {
     /// # }
     /// ```
     pub fn build_info(&self) -> Result<ServerBuildInfo, ServerBuildInfoError> {
-        if !self.server_jar.exists() {
-            return Err(ServerBuildInfoError::ServerJarNotFound {
-                path: self.server_jar.to_owned(),
-            });
-        }
-
-        let command = "java";
-        let output = std::process::Command::new(command)
-            .current_dir(&self.path)
-            .args([
-                "-jar",
-                self.server_jar
-                    .to_str()
-                    .expect("expected path to server.jar to be valid unicode"),
-                "--version",
-            ])
-            .output()
-            .map_err(|err| ServerBuildInfoError::CommandFailed {
-                command: command.to_owned(),
-                source: err.into(),
-            })?;
+        ServerBuildInfo::from_server_jar(&self.server_jar, &self.java)
+    }
 
-        let current_version = output
-            .stdout
-            .lines()
-            .last()
-            .and_then(|line| {
-                let line = line.ok()?;
-                let mut parts = line.split("-"); // [version]-[build]-[commit_hash]
-                let version = parts.next()?.to_owned();
-                let build = parts.next()?.parse().ok()?;
-                let commit_hash = parts.next()?.to_owned();
-                Some(ServerBuildInfo::new(version, build, commit_hash))
-            })
-            .ok_or_else(|| ServerBuildInfoError::ParseFailed)?;
+    /// Get the version of Minecraft the current `server.jar` is running, based on its file name.
+    ///
+    /// The `server.jar` file is a symbolic link to a server JAR downloaded from PaperMC, which
+    /// keeps its original file name (e.g., `paper-1.21.6-34.jar`). Reading the link is much faster
+    /// than [`Self::build_info`], but the result doesn't include the commit hash.
+    ///
+    /// Returns `None` if `server.jar` is not a symbolic link or the file name is not recognized.
+    pub fn linked_build_info(&self) -> Option<ServerBuildInfo> {
+        let target = std::fs::read_link(&self.server_jar).ok()?;
+        let name = target.file_name()?.to_str()?;
+        ServerBuildInfo::from_download_name(name)
+    }
 
-        Ok(current_version)
+    /// Get the version of Minecraft the current `server.jar` is running.
+    ///
+    /// This function tries [`Self::linked_build_info`] first, and only falls back to the much
+    /// slower [`Self::build_info`] if the file name of the server JAR is not recognized.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the fallback to [`Self::build_info`] fails.
+    pub fn installed_build_info(&self) -> Result<ServerBuildInfo, ServerBuildInfoError> {
+        match self.linked_build_info() {
+            Some(build_info) => Ok(build_info),
+            None => self.build_info(),
+        }
     }
 
     /// Check whether the Minecraft EULA (End User License Agreement) has been accepted.
     ///
-    /// This function reads the server's `eula.txt` file and searches for the string `eula=true`.
+    /// This function reads the server's `eula.txt` file and checks whether `eula` is set to `true`,
+    /// the same way the server itself would (ignoring comments, whitespace, and case).
     ///
     /// # Errors
     ///
@@ -210,21 +242,98 @@ impl Server {
     /// - There is a problem reading the `eula.txt` file.
     pub fn has_accepted_eula(&self) -> Result<bool, std::io::Error> {
         let contents = std::fs::read_to_string(self.eula_txt())?;
-        Ok(contents.contains("eula=true"))
+        Ok(parse_eula(&contents))
     }
+
+    /// Accept the Minecraft EULA (End User License Agreement) by writing `eula=true` to the
+    /// server's `eula.txt` file.
+    ///
+    /// The comments the server writes to `eula.txt` are kept. If the file doesn't exist yet, it is
+    /// created.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - There is a problem reading or writing the `eula.txt` file.
+    pub fn accept_eula(&self) -> Result<(), std::io::Error> {
+        let contents = match std::fs::read_to_string(self.eula_txt()) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+
+        let mut lines = contents
+            .lines()
+            .filter(|line| {
+                let key = line.split_once(['=', ':']).map(|(key, _)| key.trim());
+                key != Some("eula")
+            })
+            .collect::<Vec<_>>();
+        lines.push("eula=true");
+
+        std::fs::write(self.eula_txt(), lines.join("\n") + "\n")
+    }
+}
+
+/// Check whether the contents of an `eula.txt` file accept the Minecraft EULA.
+///
+/// `eula.txt` uses the Java properties format, so comments start with `#` or `!`, the key and
+/// value can be separated by `=` or `:` with optional whitespace, and the last value wins.
+fn parse_eula(contents: &str) -> bool {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with(['#', '!']))
+        .filter_map(|line| line.split_once(['=', ':']))
+        .rfind(|(key, _)| key.trim() == "eula")
+        .is_some_and(|(_, value)| value.trim().eq_ignore_ascii_case("true"))
 }
 
 /// Describes basic version information about a PaperMC server JAR file.
-pub struct ServerBuildInfo(String, i64, String);
+pub struct ServerBuildInfo(String, i64, Option<String>);
 
 impl ServerBuildInfo {
     /// Describes a server JAR build.
-    pub fn new(version: String, build: i64, commit_hash: String) -> Self {
+    pub fn new(version: String, build: i64, commit_hash: Option<String>) -> Self {
         Self(version, build, commit_hash)
     }
 
+    /// Parse a server JAR's build information from the file name PaperMC gave it.
+    ///
+    /// PaperMC names its server JARs `paper-[version]-[build].jar`. Returns `None` if `name`
+    /// does not follow this format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use axiom::package::ServerBuildInfo;
+    ///
+    /// # fn main() {
+    /// let build_info = ServerBuildInfo::from_download_name("paper-1.21.6-34.jar")
+    ///     .expect("expected hard-coded input to be valid");
+    /// assert_eq!(build_info.version(), "1.21.6");
+    /// assert_eq!(build_info.build(), 34);
+    /// assert!(build_info.commit_hash().is_none());
+    /// # }
+    /// ```
+    pub fn from_download_name(name: &str) -> Option<Self> {
+        // Split on the last dash, since some versions contain dashes too (e.g., `1.21-pre1`).
+        let (version, build) = name
+            .strip_prefix("paper-")?
+            .strip_suffix(".jar")?
+            .rsplit_once('-')?;
+
+        if version.is_empty() {
+            return None;
+        }
+
+        let build = build.parse().ok()?;
+        Some(Self::new(version.to_owned(), build, None))
+    }
+
     /// Represents a server JAR's build information after parsing the output from running the JAR
-    /// with `--version`.
+    /// with `--version`, using the `java` executable.
     ///
     /// # Examples
     ///
@@ -232,11 +341,12 @@ impl ServerBuildInfo {
     /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let server_jar_path = std::env::current_dir()?.join("server.jar");
     /// assert!(server_jar_path.exists());
-    /// assert!(axiom::package::ServerBuildInfo::from_server_jar(&server_jar_path).is_ok());
+    /// let java = std::path::Path::new("java");
+    /// assert!(axiom::package::ServerBuildInfo::from_server_jar(&server_jar_path, java).is_ok());
     /// # Ok(())
     /// # }
     /// ```
-    pub fn from_server_jar<P>(path: P) -> Result<Self, ServerBuildInfoError>
+    pub fn from_server_jar<P>(path: P, java: &std::path::Path) -> Result<Self, ServerBuildInfoError>
     where
         P: AsRef<std::path::Path>,
     {
@@ -248,18 +358,20 @@ impl ServerBuildInfo {
             });
         }
 
-        let command = "java";
-        let output = std::process::Command::new(command)
-            .current_dir(path.parent().unwrap())
-            .args([
-                "-jar",
-                path.to_str()
-                    .expect("expected path to server.jar to be valid unicode"),
-                "--version",
-            ])
+        // Run the JAR from its own directory, since it writes a few files on its first run.
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => std::path::Path::new("."),
+        };
+
+        let output = std::process::Command::new(java)
+            .current_dir(directory)
+            .arg("-jar")
+            .arg(path)
+            .arg("--version")
             .output()
             .map_err(|err| ServerBuildInfoError::CommandFailed {
-                command: command.to_owned(),
+                command: java.display().to_string(),
                 source: err.into(),
             })?;
 
@@ -273,7 +385,7 @@ impl ServerBuildInfo {
                 let version = parts.next()?.to_owned();
                 let build = parts.next()?.parse().ok()?;
                 let commit_hash = parts.next()?.to_owned();
-                Some(Self::new(version, build, commit_hash))
+                Some(Self::new(version, build, Some(commit_hash)))
             })
             .ok_or_else(|| ServerBuildInfoError::ParseFailed)?;
 
@@ -293,9 +405,9 @@ impl ServerBuildInfo {
         self.1
     }
 
-    /// Get the git commit hash for the current build.
-    pub fn commit_hash(&self) -> &str {
-        &self.2
+    /// Get the git commit hash for the current build, if it is known.
+    pub fn commit_hash(&self) -> Option<&str> {
+        self.2.as_deref()
     }
 }
 
@@ -321,11 +433,7 @@ pub enum ServerBuildInfoError {
 impl std::fmt::Display for ServerBuildInfoError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::ServerJarNotFound { path } => write!(
-                f,
-                "could not find 'server.jar' in {}",
-                path.parent().unwrap().display()
-            ),
+            Self::ServerJarNotFound { path } => write!(f, "could not find {}", path.display()),
             Self::CommandFailed { command, source: _ } => {
                 write!(f, "failed to execute command '{}'", command)
             }
@@ -334,6 +442,18 @@ impl std::fmt::Display for ServerBuildInfoError {
     }
 }
 
+impl ServerBuildInfoError {
+    /// Check whether the error was caused by the command (e.g., `java`) not being installed.
+    pub fn is_command_not_found(&self) -> bool {
+        match self {
+            Self::CommandFailed { command: _, source } => source
+                .downcast_ref::<std::io::Error>()
+                .is_some_and(|err| err.kind() == std::io::ErrorKind::NotFound),
+            _ => false,
+        }
+    }
+}
+
 impl std::error::Error for ServerBuildInfoError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
@@ -343,3 +463,49 @@ impl std::error::Error for ServerBuildInfoError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_download_name() {
+        let input = [
+            ("paper-1.21.6-34.jar", Some(("1.21.6", 34))),
+            ("paper-1.21-pre1-7.jar", Some(("1.21-pre1", 7))),
+            ("paper-1.21.6.jar", None),
+            ("paper-1.21.6-latest.jar", None),
+            ("paper--34.jar", None),
+            ("purpur-1.21.6-34.jar", None),
+            ("server.jar", None),
+        ];
+
+        for (name, expected) in input.into_iter() {
+            let build_info = ServerBuildInfo::from_download_name(name);
+            let actual = build_info.as_ref().map(|b| (b.version(), b.build()));
+            assert_eq!(actual, expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_parse_eula() {
+        let input = [
+            ("eula=true", true),
+            ("eula = TRUE", true),
+            ("eula=true   \n", true),
+            ("  eula=true\t", true),
+            ("eula: true", true),
+            ("#eula=true", false),
+            ("# eula=true\neula=false", false),
+            ("eula=false\neula=true", true),
+            ("eula=false", false),
+            ("eula=truest", false),
+            ("not_eula=true", false),
+            ("", false),
+        ];
+
+        for (contents, expected) in input.into_iter() {
+            assert_eq!(parse_eula(contents), expected, "{contents:?}");
+        }
+    }
+}
@@ -1,8 +1,7 @@
-use super::BASE_URL;
 use super::RequestError;
 
 /// Represents an official release for a PaperMC Minecraft server JAR file.
-#[derive(Debug, Clone, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct Build {
     /// The version of Minecraft this build is intended for.
     #[serde(skip)]
@@ -20,23 +19,40 @@ pub struct Build {
 }
 
 /// Describes which channel a build was released under.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum Channel {
     /// Indicates a stable build.
     Default,
     /// Indicates an experimental build.
     Experimental,
+    /// Indicates a channel this version of Axiom doesn't know about yet (e.g., if PaperMC adds
+    /// an `alpha` channel).
+    #[serde(other)]
+    Unknown,
 }
 
-#[derive(Debug, Clone, serde::Deserialize)]
+impl std::fmt::Display for Channel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::Experimental => write!(f, "experimental"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 struct Downloads {
     application: Application,
 }
 
-#[derive(Debug, Clone, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 struct Application {
     name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sha256: Option<String>,
 }
 
 impl Build {
@@ -53,6 +69,7 @@ impl Build {
             downloads: Downloads {
                 application: Application {
                     name: download_name,
+                    sha256: None,
                 },
             },
         }
@@ -79,6 +96,11 @@ impl Build {
         self.number
     }
 
+    /// Indicates which channel the build was released under.
+    pub fn channel(&self) -> Channel {
+        self.channel
+    }
+
     /// Indicates if the build was released under the default channel.
     pub fn stable(&self) -> bool {
         self.channel == Channel::Default
@@ -94,6 +116,13 @@ impl Build {
         &self.downloads.application.name
     }
 
+    /// The SHA-256 checksum of the server JAR file, as a hexadecimal string.
+    ///
+    /// This is only known for builds that came from PaperMC (not for [`Build::new`]).
+    pub fn sha256(&self) -> Option<&str> {
+        self.downloads.application.sha256.as_deref()
+    }
+
     /// Gets the server JAR file and returns its contents as raw bytes.
     ///
     /// This function calls the PaperMC API to get the contents of server JAR file.
@@ -107,39 +136,136 @@ impl Build {
     /// # Examples
     ///
     /// ```no_run
-    /// fn main() {
-    ///     // 1. Get the bytes from PaperMC.
-    ///     // 2. Write them to a file.
-    ///     // 3. Run the file to generate the Minecraft server.
-    /// }
+    /// // 1. Get the bytes from PaperMC.
+    /// // 2. Write them to a file.
+    /// // 3. Run the file to generate the Minecraft server.
     /// ```
     pub fn download(
         &self,
         timeout: std::time::Duration,
     ) -> Result<Vec<u8>, super::error::RequestError> {
+        self.download_with(&super::HttpClient, timeout)
+    }
+
+    /// Like [`Self::download`], but using `client` to contact PaperMC.
+    pub fn download_with(
+        &self,
+        client: &dyn super::PaperClient,
+        timeout: std::time::Duration,
+    ) -> Result<Vec<u8>, RequestError> {
+        assert!(
+            !self.version.is_empty(),
+            "use `with_version` to set the Minecraft version"
+        );
+        client.download(
+            &self.version,
+            self.number,
+            &self.downloads.application.name,
+            timeout,
+        )
+    }
+
+    /// Like [`Self::download`], but calls `progress` with the number of bytes received so far,
+    /// and the total number of bytes (if known), as the file is downloaded.
+    pub fn download_with_progress(
+        &self,
+        timeout: std::time::Duration,
+        progress: &mut dyn FnMut(u64, Option<u64>),
+    ) -> Result<Vec<u8>, RequestError> {
+        assert!(
+            !self.version.is_empty(),
+            "use `with_version` to set the Minecraft version"
+        );
+        super::PaperClient::download_with_progress(
+            &super::HttpClient,
+            &self.version,
+            self.number,
+            &self.downloads.application.name,
+            timeout,
+            progress,
+        )
+    }
+
+    /// Download the server JAR file straight into `destination`, calling `progress` with the
+    /// number of bytes received so far, and the total number of bytes (if known).
+    ///
+    /// Unlike [`Self::download`], this doesn't keep the whole file in memory, and it can recover
+    /// from a dropped connection. The file is written to `destination` with `.part` appended, and
+    /// only renamed to `destination` once it is complete and matches [`Self::sha256`] (if known).
+    /// If the download fails part of the way through, calling this again resumes it from where it
+    /// left off, as long as PaperMC supports it.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - The download fails, even after resuming it a few times.
+    /// - The downloaded file does not match [`Self::sha256`].
+    /// - There is a problem writing to `destination`.
+    pub fn download_to(
+        &self,
+        destination: &std::path::Path,
+        timeout: std::time::Duration,
+        progress: &mut dyn FnMut(u64, Option<u64>),
+    ) -> Result<(), RequestError> {
         assert!(
             !self.version.is_empty(),
             "use `with_version` to set the Minecraft version"
         );
-        let url = format!(
-            "{}/projects/paper/versions/{}/builds/{}/downloads/{}",
-            BASE_URL, self.version, self.number, self.downloads.application.name
+        let url = super::HttpClient::download_url(
+            &self.version,
+            self.number,
+            &self.downloads.application.name,
+        );
+        super::HttpClient.download_to(&url, destination, self.sha256(), timeout, progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel() {
+        let channel = |name: &str| serde_json::from_str::<Channel>(&format!("\"{name}\"")).unwrap();
+
+        assert_eq!(channel("default"), Channel::Default);
+        assert_eq!(channel("experimental"), Channel::Experimental);
+        assert_eq!(channel("alpha"), Channel::Unknown);
+
+        for channel in [Channel::Default, Channel::Experimental, Channel::Unknown] {
+            let json = serde_json::to_string(&channel).unwrap();
+            assert_eq!(json, format!("\"{channel}\""));
+        }
+
+        let build = Build::new("1.21.6".to_owned(), 1, Channel::Unknown, String::new());
+        assert!(!build.stable());
+        assert!(!build.experimental());
+    }
+
+    #[test]
+    fn test_download_with() {
+        let build = Build::new(
+            "1.21.6".to_owned(),
+            34,
+            Channel::Default,
+            "paper-1.21.6-34.jar".to_owned(),
         );
-        let client = reqwest::blocking::Client::new();
-        let response = client
-            .get(&url)
-            .timeout(timeout)
-            .send()
-            .and_then(|response| response.error_for_status())
-            .map_err(RequestError::request_failed)?;
-
-        debug_assert!(response.status().is_success());
-
-        let bytes = response
-            .bytes()
-            .map_err(RequestError::response_timed_out)?
-            .to_vec();
-
-        Ok(bytes)
+        let timeout = std::time::Duration::from_secs(1);
+
+        let bytes = build
+            .download_with(&super::super::client::MockClient, timeout)
+            .unwrap();
+        assert_eq!(bytes, b"1.21.6:34:paper-1.21.6-34.jar");
+
+        // Clients that can't report progress as they go report it once at the end.
+        let mut reported = Vec::new();
+        let client: &dyn super::super::PaperClient = &super::super::client::MockClient;
+        let bytes = client
+            .download_with_progress("1.21.6", 34, "a.jar", timeout, &mut |received, total| {
+                reported.push((received, total))
+            })
+            .unwrap();
+        assert_eq!(reported, [(bytes.len() as u64, Some(bytes.len() as u64))]);
     }
 }
@@ -0,0 +1,414 @@
+use std::io::{Read, Write};
+
+use super::BASE_URL;
+use super::RequestError;
+
+/// How many times to try downloading a server JAR before giving up, when the connection keeps
+/// dropping part of the way through.
+const DOWNLOAD_ATTEMPTS: u32 = 3;
+
+/// Describes how to reach the endpoints of the PaperMC API that Axiom uses.
+///
+/// [`HttpClient`] sends real requests to PaperMC. Other implementations can be passed to
+/// [`super::versions_with`], [`super::Version::builds_with`], and
+/// [`super::Build::download_with`] instead (e.g., to test them without a network connection).
+pub trait PaperClient {
+    /// Get the raw JSON describing the Paper project, which lists the supported versions.
+    fn project(&self) -> Result<String, RequestError>;
+
+    /// Get the raw JSON listing every build for `version`.
+    fn builds(&self, version: &str) -> Result<String, RequestError>;
+
+    /// Get the contents of the server JAR file called `name`, from build `build` of `version`.
+    fn download(
+        &self,
+        version: &str,
+        build: i64,
+        name: &str,
+        timeout: std::time::Duration,
+    ) -> Result<Vec<u8>, RequestError>;
+
+    /// Like [`Self::download`], but calls `progress` with the number of bytes received so far,
+    /// and the total number of bytes (if known), as the file is downloaded.
+    ///
+    /// By default, progress is only reported once the whole file has been received.
+    fn download_with_progress(
+        &self,
+        version: &str,
+        build: i64,
+        name: &str,
+        timeout: std::time::Duration,
+        progress: &mut dyn FnMut(u64, Option<u64>),
+    ) -> Result<Vec<u8>, RequestError> {
+        let bytes = self.download(version, build, name, timeout)?;
+        let length = bytes.len() as u64;
+        progress(length, Some(length));
+        Ok(bytes)
+    }
+}
+
+/// Sends requests to the real PaperMC API.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HttpClient;
+
+impl HttpClient {
+    fn get(&self, url: &str) -> Result<reqwest::blocking::Response, RequestError> {
+        let response = super::client()
+            .get(url)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map_err(RequestError::request_failed)?;
+
+        debug_assert!(response.status().is_success());
+
+        Ok(response)
+    }
+
+    /// Get the URL of the server JAR file called `name`, from build `build` of `version`.
+    pub(crate) fn download_url(version: &str, build: i64, name: &str) -> String {
+        format!(
+            "{}/projects/paper/versions/{}/builds/{}/downloads/{}",
+            BASE_URL, version, build, name
+        )
+    }
+
+    /// Download the file at `url` into `destination`, calling `progress` with the number of bytes
+    /// received so far, and the total number of bytes (if known).
+    ///
+    /// The file is written to `destination` with `.part` appended, and only renamed once the
+    /// whole file has been received and matches `sha256` (if known). If the connection drops, the
+    /// download is resumed from the end of the `.part` file, both by the next attempt and by the
+    /// next call.
+    pub(crate) fn download_to(
+        &self,
+        url: &str,
+        destination: &std::path::Path,
+        sha256: Option<&str>,
+        timeout: std::time::Duration,
+        progress: &mut dyn FnMut(u64, Option<u64>),
+    ) -> Result<(), RequestError> {
+        let mut part = destination.as_os_str().to_owned();
+        part.push(".part");
+        let part = std::path::PathBuf::from(part);
+
+        let mut attempt = 1;
+        loop {
+            match self.download_part(url, &part, timeout, progress) {
+                Ok(()) => break,
+                // Only a dropped connection is worth resuming; other errors would happen again.
+                Err(RequestError::ResponseTimedOut { .. }) if attempt < DOWNLOAD_ATTEMPTS => {
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        if let Some(expected) = sha256 {
+            let actual =
+                sha256_file(&part).map_err(|err| RequestError::write_failed(&part, err))?;
+
+            if !actual.eq_ignore_ascii_case(expected) {
+                // Resuming would only append to the corrupted file, so start over next time.
+                std::fs::remove_file(&part).ok();
+                return Err(RequestError::ChecksumMismatch {
+                    expected: expected.to_owned(),
+                    actual,
+                });
+            }
+        }
+
+        std::fs::rename(&part, destination)
+            .map_err(|err| RequestError::write_failed(destination, err))
+    }
+
+    /// Download the rest of the file at `url` into `part`, which may already contain the start of
+    /// it.
+    fn download_part(
+        &self,
+        url: &str,
+        part: &std::path::Path,
+        timeout: std::time::Duration,
+        progress: &mut dyn FnMut(u64, Option<u64>),
+    ) -> Result<(), RequestError> {
+        let mut offset = part.metadata().map_or(0, |metadata| metadata.len());
+        let mut request = super::client().get(url).timeout(timeout);
+
+        if offset > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={offset}-"));
+        }
+
+        let mut response = request.send().map_err(RequestError::request_failed)?;
+
+        // The `.part` file is already as long as (or longer than) the whole file, so it can't be
+        // trusted; start over.
+        if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            offset = 0;
+            response = super::client()
+                .get(url)
+                .timeout(timeout)
+                .send()
+                .map_err(RequestError::request_failed)?;
+        }
+
+        let mut response = response
+            .error_for_status()
+            .map_err(RequestError::request_failed)?;
+
+        // Servers that don't support ranges send the whole file again.
+        if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            offset = 0;
+        }
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(offset > 0)
+            .truncate(offset == 0)
+            .open(part)
+            .map_err(|err| RequestError::write_failed(part, err))?;
+
+        let total = response.content_length().map(|length| offset + length);
+        let mut received = offset;
+        let mut buffer = [0u8; 64 * 1024];
+        progress(received, total);
+
+        loop {
+            let length = response
+                .read(&mut buffer)
+                .map_err(RequestError::response_timed_out)?;
+            if length == 0 {
+                break;
+            }
+
+            file.write_all(&buffer[..length])
+                .map_err(|err| RequestError::write_failed(part, err))?;
+            received += length as u64;
+            progress(received, total);
+        }
+
+        // A connection that closes early without an error still leaves the file incomplete.
+        if total.is_some_and(|total| received < total) {
+            return Err(RequestError::response_timed_out(format!(
+                "connection closed after {received} of {} bytes",
+                total.unwrap_or_default()
+            )));
+        }
+
+        file.flush()
+            .map_err(|err| RequestError::write_failed(part, err))
+    }
+}
+
+/// Calculate the SHA-256 checksum of the file at `path`, as a hexadecimal string.
+fn sha256_file(path: &std::path::Path) -> std::io::Result<String> {
+    use sha2::Digest;
+
+    let mut hasher = sha2::Sha256::new();
+    std::io::copy(&mut std::fs::File::open(path)?, &mut hasher)?;
+
+    let checksum = hasher.finalize();
+    Ok(checksum.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+impl PaperClient for HttpClient {
+    fn project(&self) -> Result<String, RequestError> {
+        let url = format!("{}/projects/paper", BASE_URL);
+        self.get(&url)?
+            .text()
+            .map_err(RequestError::response_timed_out)
+    }
+
+    fn builds(&self, version: &str) -> Result<String, RequestError> {
+        let url = format!("{}/projects/paper/versions/{}/builds", BASE_URL, version);
+        self.get(&url)?
+            .text()
+            .map_err(RequestError::response_timed_out)
+    }
+
+    fn download(
+        &self,
+        version: &str,
+        build: i64,
+        name: &str,
+        timeout: std::time::Duration,
+    ) -> Result<Vec<u8>, RequestError> {
+        self.download_with_progress(version, build, name, timeout, &mut |_, _| {})
+    }
+
+    fn download_with_progress(
+        &self,
+        version: &str,
+        build: i64,
+        name: &str,
+        timeout: std::time::Duration,
+        progress: &mut dyn FnMut(u64, Option<u64>),
+    ) -> Result<Vec<u8>, RequestError> {
+        let url = Self::download_url(version, build, name);
+        let mut response = super::client()
+            .get(&url)
+            .timeout(timeout)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map_err(RequestError::request_failed)?;
+
+        debug_assert!(response.status().is_success());
+
+        let total = response.content_length();
+        let mut bytes = Vec::with_capacity(total.unwrap_or(0) as usize);
+        let mut buffer = [0u8; 64 * 1024];
+        progress(0, total);
+
+        loop {
+            let length = response
+                .read(&mut buffer)
+                .map_err(RequestError::response_timed_out)?;
+            if length == 0 {
+                break;
+            }
+
+            bytes.extend_from_slice(&buffer[..length]);
+            progress(bytes.len() as u64, total);
+        }
+
+        Ok(bytes)
+    }
+}
+
+/// Answers requests using the JSON fixtures in `src/paper/fixtures`, instead of PaperMC.
+///
+/// Versions without a fixture fail as if the request had failed.
+#[cfg(test)]
+pub(crate) struct MockClient;
+
+#[cfg(test)]
+impl PaperClient for MockClient {
+    fn project(&self) -> Result<String, RequestError> {
+        Ok(include_str!("fixtures/project.json").to_owned())
+    }
+
+    fn builds(&self, version: &str) -> Result<String, RequestError> {
+        match version {
+            "1.21.6" => Ok(include_str!("fixtures/builds-1.21.6.json").to_owned()),
+            // Something that isn't JSON, like an error page from a proxy.
+            "1.21.5" => Ok("<html>502 Bad Gateway</html>".to_owned()),
+            _ => Err(RequestError::request_failed(format!(
+                "404 Not Found: {version}"
+            ))),
+        }
+    }
+
+    fn download(
+        &self,
+        version: &str,
+        build: i64,
+        name: &str,
+        _: std::time::Duration,
+    ) -> Result<Vec<u8>, RequestError> {
+        Ok(format!("{version}:{build}:{name}").into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serve `content` over HTTP once for each of `responses`, which says whether to honor the
+    /// `Range` header, and whether to drop the connection halfway through the body.
+    fn serve(content: &'static [u8], responses: Vec<(bool, bool)>) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/paper.jar", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            for (honor_range, drop_connection) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut byte = [0; 1];
+
+                while !request.ends_with(b"\r\n\r\n") {
+                    stream.read_exact(&mut byte).unwrap();
+                    request.push(byte[0]);
+                }
+
+                let start = String::from_utf8_lossy(&request)
+                    .lines()
+                    .find_map(|line| {
+                        line.to_ascii_lowercase()
+                            .strip_prefix("range: bytes=")?
+                            .strip_suffix('-')?
+                            .parse::<usize>()
+                            .ok()
+                    })
+                    .filter(|_| honor_range);
+
+                let (status, body) = match start {
+                    Some(start) => ("206 Partial Content", &content[start..]),
+                    None => ("200 OK", content),
+                };
+                let sent = match drop_connection {
+                    true => &body[..body.len() / 2],
+                    false => body,
+                };
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                )
+                .unwrap();
+                stream.write_all(sent).unwrap();
+            }
+        });
+
+        url
+    }
+
+    #[test]
+    fn test_download_to() {
+        const CONTENT: &[u8] = b"The quick brown fox jumps over the lazy dog";
+        const SHA256: &str = "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592";
+
+        let root = tempfile::tempdir().unwrap();
+        let destination = root.path().join("paper.jar");
+        let part = root.path().join("paper.jar.part");
+        let timeout = std::time::Duration::from_secs(5);
+
+        // Resume from an existing `.part` file.
+        std::fs::write(&part, &CONTENT[..10]).unwrap();
+        let url = serve(CONTENT, vec![(true, false)]);
+        let mut reported = Vec::new();
+        HttpClient
+            .download_to(
+                &url,
+                &destination,
+                Some(SHA256),
+                timeout,
+                &mut |received, total| reported.push((received, total)),
+            )
+            .unwrap();
+        assert_eq!(std::fs::read(&destination).unwrap(), CONTENT);
+        assert!(!part.exists());
+        assert_eq!(reported.first(), Some(&(10, Some(CONTENT.len() as u64))));
+
+        // Start over if the server ignores the `Range` header.
+        std::fs::write(&part, b"garbage").unwrap();
+        let url = serve(CONTENT, vec![(false, false)]);
+        HttpClient
+            .download_to(&url, &destination, Some(SHA256), timeout, &mut |_, _| {})
+            .unwrap();
+        assert_eq!(std::fs::read(&destination).unwrap(), CONTENT);
+
+        // Resume after the connection drops.
+        let url = serve(CONTENT, vec![(true, true), (true, false)]);
+        HttpClient
+            .download_to(&url, &destination, Some(SHA256), timeout, &mut |_, _| {})
+            .unwrap();
+        assert_eq!(std::fs::read(&destination).unwrap(), CONTENT);
+
+        // A corrupted download is thrown away.
+        let url = serve(b"The quick brown fox", vec![(true, false)]);
+        let result =
+            HttpClient.download_to(&url, &destination, Some(SHA256), timeout, &mut |_, _| {});
+        assert!(matches!(result, Err(RequestError::ChecksumMismatch { .. })));
+        assert!(!part.exists());
+    }
+}
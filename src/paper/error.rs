@@ -18,6 +18,20 @@ pub enum RequestError {
         /// The underlying error that occurred while attempting to parse the response.
         source: Box<StdError>,
     },
+    /// There was a problem saving a download to disk.
+    WriteFailed {
+        /// The path that was being written to.
+        path: std::path::PathBuf,
+        /// The underlying error that caused the failure.
+        source: std::io::Error,
+    },
+    /// The downloaded file does not match the checksum published by PaperMC.
+    ChecksumMismatch {
+        /// The SHA-256 checksum PaperMC published, as a hexadecimal string.
+        expected: String,
+        /// The SHA-256 checksum of the downloaded file, as a hexadecimal string.
+        actual: String,
+    },
 }
 
 impl std::fmt::Display for RequestError {
@@ -26,6 +40,13 @@ impl std::fmt::Display for RequestError {
             Self::RequestFailed { source: _ } => write!(f, "failed to send request to PaperMC API"),
             Self::ResponseTimedOut { source: _ } => write!(f, "failed to get response body"),
             Self::ParseResponseFailed { source: _ } => write!(f, "failed to parse response body"),
+            Self::WriteFailed { path, source: _ } => {
+                write!(f, "failed to write to {}", path.display())
+            }
+            Self::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "downloaded file is corrupted (expected SHA-256 {expected}, got {actual})"
+            ),
         }
     }
 }
@@ -36,6 +57,8 @@ impl std::error::Error for RequestError {
             Self::RequestFailed { source } => Some(source.as_ref()),
             Self::ResponseTimedOut { source } => Some(source.as_ref()),
             Self::ParseResponseFailed { source } => Some(source.as_ref()),
+            Self::WriteFailed { path: _, source } => Some(source),
+            Self::ChecksumMismatch { .. } => None,
         }
     }
 }
@@ -61,4 +84,12 @@ impl RequestError {
             source: source.into(),
         }
     }
+
+    /// Creates an error indicating a failure to save a download to `path`.
+    pub fn write_failed(path: impl Into<std::path::PathBuf>, source: std::io::Error) -> Self {
+        Self::WriteFailed {
+            path: path.into(),
+            source,
+        }
+    }
 }
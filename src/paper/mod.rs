@@ -23,28 +23,32 @@
 //! ```
 
 mod build;
+mod client;
 mod error;
 mod version;
 
 pub use build::{Build, Channel};
+pub use client::{HttpClient, PaperClient};
 pub use error::RequestError;
-pub use version::Version;
+pub use version::{BUILDS_CACHE_TTL, Version};
 
 pub(crate) const BASE_URL: &str = "https://api.papermc.io/v2";
 
+/// Get the HTTP client shared by every request, so the connection pool (and TLS session) can be
+/// reused when making several requests in a row.
+pub(crate) fn client() -> &'static reqwest::blocking::Client {
+    static CLIENT: std::sync::OnceLock<reqwest::blocking::Client> = std::sync::OnceLock::new();
+    CLIENT.get_or_init(reqwest::blocking::Client::new)
+}
+
 /// Get all of the Minecraft versions that PaperMC supports.
 pub fn versions() -> Result<Vec<Version>, RequestError> {
-    let url = format!("{}/projects/paper", BASE_URL);
-    let client = reqwest::blocking::Client::new();
-    let response = client
-        .get(&url)
-        .send()
-        .and_then(|response| response.error_for_status())
-        .map_err(RequestError::request_failed)?;
-
-    debug_assert!(response.status().is_success());
+    versions_with(&HttpClient)
+}
 
-    let text = response.text().map_err(RequestError::response_timed_out)?;
+/// Like [`versions`], but using `client` to contact PaperMC.
+pub fn versions_with(client: &dyn PaperClient) -> Result<Vec<Version>, RequestError> {
+    let text = client.project()?;
 
     #[derive(serde::Deserialize)]
     struct Response {
@@ -60,3 +64,90 @@ pub fn versions() -> Result<Vec<Version>, RequestError> {
 
     Ok(versions)
 }
+
+/// Get the builds that were previously downloaded into `directory`, oldest to newest.
+///
+/// This doesn't make any requests to PaperMC, which is useful when working offline. Builds are
+/// recognized by the file name PaperMC gives each server JAR (`paper-[version]-[build].jar`).
+/// Because the release channel isn't part of the file name, every build is treated as stable.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() {
+/// let jars = dirs::cache_dir().unwrap().join("axiom");
+/// for build in axiom::paper::cached_builds(&jars) {
+///     println!("{} (#{})", build.version(), build.number());
+/// }
+/// # }
+/// ```
+pub fn cached_builds(directory: &std::path::Path) -> Vec<Build> {
+    let mut builds = std::fs::read_dir(directory)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let build_info = crate::package::ServerBuildInfo::from_download_name(&name)?;
+            let build = Build::new(
+                build_info.version().to_owned(),
+                build_info.build(),
+                Channel::Default,
+                name,
+            );
+            Some(build)
+        })
+        .collect::<Vec<_>>();
+
+    builds.sort_by(|a, b| {
+        version_key(a.version())
+            .cmp(&version_key(b.version()))
+            .then(a.number().cmp(&b.number()))
+    });
+
+    builds
+}
+
+/// Get the Minecraft versions that have at least one build downloaded into `directory`, oldest to
+/// newest.
+///
+/// This is the offline counterpart to [`versions`]; see [`cached_builds`].
+pub fn cached_versions(directory: &std::path::Path) -> Vec<Version> {
+    let mut versions = cached_builds(directory)
+        .into_iter()
+        .map(|build| build.version().to_owned())
+        .collect::<Vec<_>>();
+    versions.dedup();
+
+    versions.into_iter().map(Version::new).collect()
+}
+
+/// Get a key for sorting Minecraft versions (e.g., `1.21.6` comes after `1.9.4`).
+pub(crate) fn version_key(version: &str) -> Vec<u64> {
+    version
+        .split(['.', '-'])
+        .map(|part| {
+            let digits = part
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect::<String>();
+            digits.parse().unwrap_or(0)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_with() {
+        let versions = versions_with(&client::MockClient).unwrap();
+        let versions = versions.iter().map(Version::as_str).collect::<Vec<_>>();
+
+        assert_eq!(
+            versions,
+            ["1.20.6", "1.21", "1.21.1", "1.21.4", "1.21.5", "1.21.6"]
+        );
+    }
+}
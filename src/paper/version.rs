@@ -1,7 +1,10 @@
-use super::BASE_URL;
 use super::Build;
 use super::RequestError;
 
+/// How long the list of builds saved by [`Version::builds_cached`] is used before asking PaperMC
+/// for it again.
+pub const BUILDS_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
 /// Represents a Minecraft version supported by PaperMC.
 #[derive(Debug, Clone)]
 pub struct Version(String);
@@ -22,6 +25,38 @@ impl Version {
         &self.0
     }
 
+    /// Compare the release order of two Minecraft versions (e.g., `1.21.6` comes after `1.9.4`).
+    ///
+    /// Release versions (including pre-releases and release candidates, like `1.21-pre1`) are
+    /// compared as semantic versions. Anything else (e.g., snapshots like `23w45a`) is compared by
+    /// its position in `versions`, which should be the chronological list returned by
+    /// [`super::versions`]. Returns `None` if the order can't be determined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    ///
+    /// use axiom::paper::Version;
+    ///
+    /// # fn main() {
+    /// let version = |v: &str| Version::new(v.to_owned());
+    /// let versions = ["1.20.2", "23w45a", "1.20.3"].map(version);
+    ///
+    /// assert_eq!(version("1.21").cmp_release(&version("1.9.4"), &versions), Some(Ordering::Greater));
+    /// assert_eq!(version("23w45a").cmp_release(&version("1.20.3"), &versions), Some(Ordering::Less));
+    /// assert_eq!(version("24w01a").cmp_release(&version("1.20.3"), &versions), None);
+    /// # }
+    /// ```
+    pub fn cmp_release(&self, other: &Self, versions: &[Version]) -> Option<std::cmp::Ordering> {
+        if let (Some(a), Some(b)) = (parse_release(&self.0), parse_release(&other.0)) {
+            return Some(a.cmp(&b));
+        }
+
+        let position = |version: &Self| versions.iter().position(|v| v.0 == version.0);
+        Some(position(self)?.cmp(&position(other)?))
+    }
+
     /// Get all of the available builds for the current version.
     ///
     /// This function sends a GET request to PaperMC to get a list of all available builds
@@ -34,25 +69,94 @@ impl Version {
     /// - There is a problem sending the request to PaperMC.
     /// - Reading the response body times out.
     pub fn builds(&self) -> Result<Vec<Build>, RequestError> {
-        let url = format!("{}/projects/paper/versions/{}/builds", BASE_URL, self.0);
-        let client = reqwest::blocking::Client::new();
-        let response = client
-            .get(&url)
-            .send()
-            .and_then(|response| response.error_for_status())
-            .map_err(RequestError::request_failed)?;
+        self.builds_with(&super::HttpClient)
+    }
 
-        debug_assert!(response.status().is_success());
+    /// Like [`Self::builds`], but using `client` to contact PaperMC.
+    pub fn builds_with(&self, client: &dyn super::PaperClient) -> Result<Vec<Build>, RequestError> {
+        let text = client.builds(&self.0)?;
+        self.parse_builds(&text)
+    }
 
-        let text = response.text().map_err(RequestError::response_timed_out)?;
+    /// Like [`Self::builds`], but reuses the list of builds saved in `directory` by a previous call
+    /// if it is less than [`BUILDS_CACHE_TTL`] old.
+    ///
+    /// The list is saved to `builds/[version].json` inside of `directory`, with everything needed
+    /// to recreate each [`Build`] without contacting PaperMC. Failing to save the list is not an
+    /// error, since the builds were still fetched successfully.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the cache is missing or stale, and [`Self::builds`]
+    /// fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use axiom::paper::Version;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let cache = dirs::cache_dir().unwrap().join("axiom");
+    ///     let version = Version::new("1.21.6".to_owned());
+    ///     let builds = version.builds_cached(&cache)?;
+    ///     // The second call reads the builds from disk instead.
+    ///     assert_eq!(version.builds_cached(&cache)?.len(), builds.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn builds_cached(&self, directory: &std::path::Path) -> Result<Vec<Build>, RequestError> {
+        let path = directory.join("builds").join(format!("{}.json", self.0));
+
+        let fresh = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age < BUILDS_CACHE_TTL);
+
+        if fresh {
+            let cached = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|text| self.parse_builds(&text).ok());
+
+            if let Some(builds) = cached {
+                return Ok(builds);
+            }
+        }
+
+        let builds = self.builds()?;
+
+        #[derive(serde::Serialize)]
+        struct Cache<'a> {
+            builds: &'a [Build],
+        }
+
+        if let Ok(contents) = serde_json::to_string(&Cache { builds: &builds }) {
+            _ = std::fs::create_dir_all(directory.join("builds"))
+                .and_then(|_| std::fs::write(&path, contents));
+        }
 
+        Ok(builds)
+    }
+
+    /// Get the newest build for the current version, using the same cache as
+    /// [`Self::builds_cached`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if [`Self::builds_cached`] fails.
+    pub fn latest_build(&self, directory: &std::path::Path) -> Result<Option<Build>, RequestError> {
+        Ok(self.builds_cached(directory)?.pop())
+    }
+
+    /// Parse the list of builds returned by PaperMC (or saved by [`Self::builds_cached`]).
+    fn parse_builds(&self, text: &str) -> Result<Vec<Build>, RequestError> {
         #[derive(serde::Deserialize)]
         struct Response {
             builds: Vec<Build>,
         }
 
         let data: Response =
-            serde_json::from_str(&text).map_err(RequestError::parse_response_failed)?;
+            serde_json::from_str(text).map_err(RequestError::parse_response_failed)?;
 
         let builds = data
             .builds
@@ -63,3 +167,102 @@ impl Version {
         Ok(builds)
     }
 }
+
+/// Parse a Minecraft release version as a semantic version.
+///
+/// Minecraft leaves out the patch number for the first release of each minor version (e.g.,
+/// `1.21`), so it is filled in before parsing.
+fn parse_release(version: &str) -> Option<semver::Version> {
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+
+    let core = match core.matches('.').count() {
+        1 => format!("{core}.0"),
+        _ => core.to_owned(),
+    };
+
+    let version = match pre {
+        Some(pre) => format!("{core}-{pre}"),
+        None => core,
+    };
+
+    semver::Version::parse(&version).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cmp_release() {
+        use std::cmp::Ordering;
+
+        let version = |v: &str| Version::new(v.to_owned());
+        let versions = ["1.20.2", "23w45a", "23w46a", "1.20.3", "1.21-pre1", "1.21"].map(version);
+        let cmp = |a: &str, b: &str| version(a).cmp_release(&version(b), &versions);
+
+        assert_eq!(cmp("1.21.6", "1.9.4"), Some(Ordering::Greater));
+        assert_eq!(cmp("1.21", "1.21.0"), Some(Ordering::Equal));
+        assert_eq!(cmp("1.21-pre1", "1.21"), Some(Ordering::Less));
+        assert_eq!(cmp("1.21-rc1", "1.21-pre1"), Some(Ordering::Greater));
+        assert_eq!(cmp("23w45a", "23w46a"), Some(Ordering::Less));
+        assert_eq!(cmp("23w46a", "1.20.2"), Some(Ordering::Greater));
+        assert_eq!(cmp("1.20.3", "23w45a"), Some(Ordering::Greater));
+        assert_eq!(cmp("24w01a", "1.21"), None);
+    }
+
+    #[test]
+    fn test_builds_with() {
+        let client = super::super::client::MockClient;
+
+        let builds = Version::new("1.21.6".to_owned())
+            .builds_with(&client)
+            .unwrap();
+        assert_eq!(builds.len(), 2);
+        assert!(builds.iter().all(|build| build.version() == "1.21.6"));
+        assert!(builds[0].experimental());
+        assert!(builds[1].stable());
+        assert_eq!(builds[1].download_name(), "paper-1.21.6-34.jar");
+        assert!(builds[1].sha256().is_some());
+
+        let result = Version::new("1.21.5".to_owned()).builds_with(&client);
+        assert!(matches!(
+            result,
+            Err(RequestError::ParseResponseFailed { .. })
+        ));
+
+        let result = Version::new("0.0.0".to_owned()).builds_with(&client);
+        assert!(matches!(result, Err(RequestError::RequestFailed { .. })));
+    }
+
+    #[test]
+    fn test_builds_cached() {
+        let directory = tempfile::tempdir().unwrap();
+        let directory = directory.path();
+        let path = directory.join("builds").join("1.21.6.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            r#"{"builds":[
+                {"build":33,"channel":"experimental","downloads":{"application":{"name":"paper-1.21.6-33.jar","sha256":"abc"}}},
+                {"build":34,"channel":"default","downloads":{"application":{"name":"paper-1.21.6-34.jar"}}}
+            ]}"#,
+        )
+        .unwrap();
+
+        // The cache was just written, so PaperMC should not be contacted.
+        let version = Version::new("1.21.6".to_owned());
+        let builds = version.builds_cached(directory).unwrap();
+        assert_eq!(builds.len(), 2);
+        assert_eq!(builds[0].version(), "1.21.6");
+        assert!(builds[0].experimental());
+        assert_eq!(builds[0].sha256(), Some("abc"));
+
+        let latest = version.latest_build(directory).unwrap().unwrap();
+        assert_eq!(latest.number(), 34);
+        assert_eq!(latest.download_name(), "paper-1.21.6-34.jar");
+        assert_eq!(latest.sha256(), None);
+    }
+}
@@ -0,0 +1,400 @@
+//! This module implements functionality for downloading the plugins declared in a manifest.
+//!
+//! # Examples
+//!
+//! To download a plugin from a direct URL:
+//!
+//! ```no_run
+//! use axiom::manifest::PluginSource;
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let source = PluginSource::Url("https://example.com/Example-1.0.0.jar".to_owned());
+//!     let download = axiom::plugin::resolve(&source)?;
+//!     let bytes = download.fetch(std::time::Duration::from_secs(60))?;
+//!     assert!(std::fs::write(download.file_name(), &bytes).is_ok());
+//!     Ok(())
+//! }
+//! ```
+
+use crate::manifest::PluginSource;
+
+type StdError = dyn std::error::Error + Send + Sync + 'static;
+
+/// The file used to record which files were installed for each plugin.
+pub const LOCKFILE_NAME: &str = "Axiom.lock";
+
+/// Describes where to download a plugin's JAR file from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Download {
+    url: String,
+    file_name: String,
+}
+
+impl Download {
+    /// Get the URL to download the plugin's JAR file from.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get the name the plugin's JAR file should be saved as.
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// Get the plugin's JAR file and return its contents as raw bytes.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - The request could not be sent, or the server responded with an error status.
+    /// - The response body could not be read before `timeout` elapsed.
+    pub fn fetch(&self, timeout: std::time::Duration) -> Result<Vec<u8>, PluginError> {
+        let response = crate::paper::client()
+            .get(&self.url)
+            .timeout(timeout)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map_err(PluginError::request_failed)?;
+
+        debug_assert!(response.status().is_success());
+
+        let bytes = response
+            .bytes()
+            .map_err(PluginError::response_timed_out)?
+            .to_vec();
+
+        Ok(bytes)
+    }
+}
+
+/// Figure out where to download the plugin described by `source` from.
+///
+/// Only direct URLs are supported for now. Plugin registries (Modrinth and Hangar) will need to
+/// be queried for the URL of the requested version, which is not implemented yet.
+///
+/// # Examples
+///
+/// ```
+/// use axiom::manifest::PluginSource;
+///
+/// # fn main() {
+/// let source = PluginSource::Url("https://example.com/Example-1.0.0.jar?v=1".to_owned());
+/// let download = axiom::plugin::resolve(&source).unwrap();
+/// assert_eq!(download.file_name(), "Example-1.0.0.jar");
+/// # }
+/// ```
+pub fn resolve(source: &PluginSource) -> Result<Download, PluginError> {
+    match source {
+        PluginSource::Url(url) => {
+            let file_name = url
+                .split(['?', '#'])
+                .next()
+                .and_then(|path| path.rsplit('/').next())
+                .filter(|name| name.ends_with(".jar"))
+                .ok_or_else(|| PluginError::InvalidUrl { url: url.clone() })?;
+
+            Ok(Download {
+                url: url.clone(),
+                file_name: file_name.to_owned(),
+            })
+        }
+        PluginSource::Modrinth { .. } => Err(PluginError::UnsupportedSource {
+            registry: "Modrinth",
+        }),
+        PluginSource::Hangar { .. } => Err(PluginError::UnsupportedSource { registry: "Hangar" }),
+    }
+}
+
+/// Describes a plugin, as read from the `plugin.yml` file inside of its JAR file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginInfo {
+    name: String,
+    version: String,
+}
+
+impl PluginInfo {
+    /// The files that may describe a plugin, in order of preference.
+    const METADATA_FILES: [&'static str; 2] = ["plugin.yml", "paper-plugin.yml"];
+
+    /// Read the plugin's name and version from the JAR file at `path`.
+    ///
+    /// JAR files are zip archives, so the `plugin.yml` file is read without extracting anything.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - The JAR file could not be opened or is not a valid zip archive.
+    /// - The JAR file doesn't contain a `plugin.yml` (or `paper-plugin.yml`) file.
+    /// - The `plugin.yml` file doesn't contain a `name` and `version`.
+    pub fn from_jar<P>(path: P) -> Result<Self, PluginError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        use std::io::Read;
+
+        let file = std::fs::File::open(path).map_err(PluginError::metadata_failed)?;
+        let mut archive = zip::ZipArchive::new(file).map_err(PluginError::metadata_failed)?;
+
+        let name = Self::METADATA_FILES
+            .into_iter()
+            .find(|name| archive.index_for_name(name).is_some())
+            .ok_or_else(|| PluginError::metadata_failed("missing plugin.yml"))?;
+
+        let mut contents = String::new();
+        archive
+            .by_name(name)
+            .map_err(PluginError::metadata_failed)?
+            .read_to_string(&mut contents)
+            .map_err(PluginError::metadata_failed)?;
+
+        Self::from_yaml(&contents)
+            .ok_or_else(|| PluginError::metadata_failed("missing name or version in plugin.yml"))
+    }
+
+    /// Read the plugin's name and version from the contents of a `plugin.yml` file.
+    ///
+    /// Only the top-level `name` and `version` keys are read, so this doesn't need a full YAML
+    /// parser. Returns `None` if either key is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use axiom::plugin::PluginInfo;
+    ///
+    /// # fn main() {
+    /// let contents = "name: Example\nversion: '1.0.0'\nmain: com.example.Example\n";
+    /// let info = PluginInfo::from_yaml(contents).unwrap();
+    /// assert_eq!(info.name(), "Example");
+    /// assert_eq!(info.version(), "1.0.0");
+    /// # }
+    /// ```
+    pub fn from_yaml(contents: &str) -> Option<Self> {
+        let mut name = None;
+        let mut version = None;
+
+        for line in contents.lines() {
+            // Nested keys are indented; only the top-level keys describe the plugin itself.
+            if line.starts_with(char::is_whitespace) {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+
+            let value = value.split(" #").next().unwrap_or_default().trim();
+            let value = value
+                .strip_prefix(['"', '\''])
+                .and_then(|value| value.strip_suffix(['"', '\'']))
+                .unwrap_or(value);
+
+            match key.trim() {
+                "name" => name = Some(value.to_owned()),
+                "version" => version = Some(value.to_owned()),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            name: name.filter(|name| !name.is_empty())?,
+            version: version.filter(|version| !version.is_empty())?,
+        })
+    }
+
+    /// Get the name of the plugin.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the version of the plugin.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
+
+/// Records which file was installed for each plugin, so later runs can tell what changed.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct Lockfile {
+    #[serde(default)]
+    plugins: std::collections::BTreeMap<String, LockedPlugin>,
+}
+
+/// Describes the file that was installed for a single plugin.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LockedPlugin {
+    source: PluginSource,
+    file_name: String,
+}
+
+impl LockedPlugin {
+    /// Record that `file_name` was installed from `source`.
+    pub fn new(source: PluginSource, file_name: String) -> Self {
+        Self { source, file_name }
+    }
+
+    /// Get the source the plugin was installed from.
+    pub fn source(&self) -> &PluginSource {
+        &self.source
+    }
+
+    /// Get the name of the plugin's JAR file in the `plugins` directory.
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+}
+
+impl Lockfile {
+    /// Read the lockfile at `path`, or return an empty lockfile if it doesn't exist yet.
+    pub fn from_file<P>(path: P) -> Result<Self, PluginError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents).map_err(PluginError::lockfile_failed),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(PluginError::lockfile_failed(err)),
+        }
+    }
+
+    /// Write the lockfile to `path`.
+    pub fn save<P>(&self, path: P) -> Result<(), PluginError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let contents = toml::to_string(self).map_err(PluginError::lockfile_failed)?;
+        std::fs::write(path, contents).map_err(PluginError::lockfile_failed)
+    }
+
+    /// Get the installed plugins, keyed by the names used in the manifest.
+    pub fn plugins(&self) -> &std::collections::BTreeMap<String, LockedPlugin> {
+        &self.plugins
+    }
+
+    /// Get what was installed for the plugin called `name`.
+    pub fn get(&self, name: &str) -> Option<&LockedPlugin> {
+        self.plugins.get(name)
+    }
+
+    /// Record what was installed for the plugin called `name`.
+    pub fn insert(&mut self, name: String, plugin: LockedPlugin) {
+        self.plugins.insert(name, plugin);
+    }
+}
+
+/// Represents errors that can occur while installing plugins.
+#[derive(Debug)]
+pub enum PluginError {
+    /// An error occurred while attempting to send the download request.
+    RequestFailed {
+        /// The underlying error that caused the request to fail.
+        source: Box<StdError>,
+    },
+    /// The download took too long to complete.
+    ResponseTimedOut {
+        /// The underlying error that caused the timeout.
+        source: Box<StdError>,
+    },
+    /// The URL does not point to a JAR file.
+    InvalidUrl {
+        /// The URL from the manifest.
+        url: String,
+    },
+    /// Downloading plugins from this plugin registry is not supported yet.
+    UnsupportedSource {
+        /// The name of the plugin registry.
+        registry: &'static str,
+    },
+    /// There was a problem reading the `plugin.yml` file from a plugin's JAR file.
+    MetadataFailed {
+        /// The underlying error that caused the failure.
+        source: Box<StdError>,
+    },
+    /// There was a problem reading or writing the lockfile.
+    LockfileFailed {
+        /// The underlying error that caused the failure.
+        source: Box<StdError>,
+    },
+}
+
+impl std::fmt::Display for PluginError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RequestFailed { source: _ } => write!(f, "failed to send download request"),
+            Self::ResponseTimedOut { source: _ } => write!(f, "failed to get response body"),
+            Self::InvalidUrl { url } => write!(f, "expected URL to end with a .jar file: {url}"),
+            Self::UnsupportedSource { registry } => {
+                write!(f, "installing plugins from {registry} is not supported yet")
+            }
+            Self::MetadataFailed { source: _ } => write!(f, "failed to read plugin.yml"),
+            Self::LockfileFailed { source: _ } => write!(f, "failed to update {LOCKFILE_NAME}"),
+        }
+    }
+}
+
+impl std::error::Error for PluginError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::RequestFailed { source } => Some(source.as_ref()),
+            Self::ResponseTimedOut { source } => Some(source.as_ref()),
+            Self::InvalidUrl { url: _ } => None,
+            Self::UnsupportedSource { registry: _ } => None,
+            Self::MetadataFailed { source } => Some(source.as_ref()),
+            Self::LockfileFailed { source } => Some(source.as_ref()),
+        }
+    }
+}
+
+impl PluginError {
+    /// Creates an error indicating that the download request has failed.
+    pub fn request_failed(source: impl Into<Box<StdError>>) -> Self {
+        Self::RequestFailed {
+            source: source.into(),
+        }
+    }
+
+    /// Creates an error indicating that the download has timed out.
+    pub fn response_timed_out(source: impl Into<Box<StdError>>) -> Self {
+        Self::ResponseTimedOut {
+            source: source.into(),
+        }
+    }
+
+    /// Creates an error indicating a failure to read a plugin's `plugin.yml` file.
+    pub fn metadata_failed(source: impl Into<Box<StdError>>) -> Self {
+        Self::MetadataFailed {
+            source: source.into(),
+        }
+    }
+
+    /// Creates an error indicating a failure to read or write the lockfile.
+    pub fn lockfile_failed(source: impl Into<Box<StdError>>) -> Self {
+        Self::LockfileFailed {
+            source: source.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_yaml() {
+        let contents = r#"
+name: "LuckPerms"
+version: 5.5.0 # The plugin version
+main: me.lucko.luckperms.bukkit.loader.BukkitLoaderPlugin
+commands:
+  luckperms:
+    name: not-the-plugin-name
+"#;
+        let info = PluginInfo::from_yaml(contents).unwrap();
+        assert_eq!(info.name(), "LuckPerms");
+        assert_eq!(info.version(), "5.5.0");
+
+        assert_eq!(PluginInfo::from_yaml("name: Example\n"), None);
+        assert_eq!(PluginInfo::from_yaml("not yaml at all"), None);
+    }
+}
@@ -0,0 +1,54 @@
+type StdError = dyn std::error::Error + Send + Sync + 'static;
+
+/// Represents errors that can occur while running tmux commands.
+#[derive(Debug)]
+pub enum TmuxError {
+    /// An error occurred while attempting to execute the `tmux` command.
+    CommandFailed {
+        /// The underlying error that caused the command to fail.
+        source: Box<StdError>,
+    },
+    /// The `tmux` command ran, but exited unsuccessfully.
+    ExitFailure {
+        /// The exit status returned by the `tmux` command.
+        status: std::process::ExitStatus,
+    },
+    /// The `tmux` executable could not be found in any of the directories in `PATH`.
+    NotInstalled,
+    /// The target window does not exist.
+    WindowNotFound {
+        /// The target string used to look up the window.
+        target: String,
+    },
+}
+
+impl std::fmt::Display for TmuxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CommandFailed { source: _ } => write!(f, "failed to execute command 'tmux'"),
+            Self::ExitFailure { status } => write!(f, "tmux command failed ({status})"),
+            Self::NotInstalled => write!(f, "could not find 'tmux' in PATH"),
+            Self::WindowNotFound { target } => write!(f, "could not find tmux window '{target}'"),
+        }
+    }
+}
+
+impl std::error::Error for TmuxError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::CommandFailed { source } => Some(source.as_ref()),
+            Self::ExitFailure { status: _ } => None,
+            Self::NotInstalled => None,
+            Self::WindowNotFound { target: _ } => None,
+        }
+    }
+}
+
+impl TmuxError {
+    /// Creates an error indicating that the `tmux` command could not be executed.
+    pub fn command_failed(source: impl Into<Box<StdError>>) -> Self {
+        Self::CommandFailed {
+            source: source.into(),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! # Tmux
+//!
+//! This module provides functionality for running Minecraft servers inside of tmux, which allows
+//! the server console to keep running in the background after the terminal is closed.
+//!
+//! # Examples
+//!
+//! To start a server in a new window and stop it again later:
+//!
+//! ```no_run
+//! use axiom::tmux::{Session, Window};
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let session = Session::new("axiom".to_owned(), "servers".to_owned());
+//!     let server = std::env::current_dir()?.join("server");
+//!     let window = Window::create(&session, "example", &server, "./start.sh")?;
+//!     assert!(window.exists()?);
+//!     window.send_keys(&["C-c"])?;
+//!     Ok(())
+//! }
+//! ```
+
+mod error;
+mod session;
+mod window;
+
+pub use error::TmuxError;
+pub use session::Session;
+pub use window::Window;
+
+/// Check whether the `tmux` executable is available.
+///
+/// Without this check, a missing `tmux` executable shows up as a confusing "No such file or
+/// directory" error from whichever command happens to run first.
+///
+/// # Errors
+///
+/// This function returns an error if:
+///
+/// - `tmux` could not be found in any of the directories in `PATH`.
+pub fn ensure_installed() -> Result<(), TmuxError> {
+    let installed = std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|path| path.join("tmux").is_file()))
+        .unwrap_or(false);
+
+    if !installed {
+        return Err(TmuxError::NotInstalled);
+    }
+
+    Ok(())
+}
+
+/// Build a `tmux` command that runs against the tmux server with the given socket name.
+pub(crate) fn command(server: &str) -> std::process::Command {
+    let mut command = std::process::Command::new("tmux");
+    command
+        .args(["-L", server])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    command
+}
+
+/// Run the `tmux` command, returning an error if it fails to execute or exits unsuccessfully.
+pub(crate) fn run(command: &mut std::process::Command) -> Result<(), TmuxError> {
+    let status = command.status().map_err(TmuxError::command_failed)?;
+
+    if !status.success() {
+        return Err(TmuxError::ExitFailure { status });
+    }
+
+    Ok(())
+}
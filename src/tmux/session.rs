@@ -0,0 +1,53 @@
+/// Represents a tmux session on a specific tmux server.
+#[derive(Debug, Clone)]
+pub struct Session {
+    /// The name of the tmux server's socket (the value passed to `tmux -L`).
+    server: String,
+
+    /// The name of the session.
+    name: String,
+}
+
+impl Session {
+    /// Represents a tmux session named `name` on the tmux server with the socket name `server`.
+    ///
+    /// The session does not need to exist yet; it is created on demand by
+    /// [`super::Window::create`].
+    pub fn new(server: String, name: String) -> Self {
+        Self { server, name }
+    }
+
+    /// Get the name of the tmux server's socket.
+    pub fn server(&self) -> &str {
+        &self.server
+    }
+
+    /// Get the name of the session.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the target string that exactly matches this session.
+    ///
+    /// The `=` prefix disables tmux's fuzzy matching, which would otherwise allow a session named
+    /// `servers` to match a target of `server`.
+    pub fn target(&self) -> String {
+        format!("={}", self.name)
+    }
+
+    /// Check whether the session is currently running.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - There is a problem executing the `tmux` command.
+    pub fn exists(&self) -> Result<bool, super::TmuxError> {
+        let status = super::command(&self.server)
+            .args(["has-session", "-t", &self.target()])
+            .status()
+            .map_err(super::TmuxError::command_failed)?;
+
+        Ok(status.success())
+    }
+}
@@ -0,0 +1,285 @@
+use super::Session;
+use super::TmuxError;
+
+/// Represents a window inside of a tmux session.
+///
+/// Each running Minecraft server gets its own window, named after its package.
+#[derive(Debug, Clone)]
+pub struct Window {
+    /// The session this window belongs to.
+    session: Session,
+
+    /// The name of the window.
+    name: String,
+}
+
+impl Window {
+    /// Represents the window named `name` inside of `session`.
+    ///
+    /// This does not create the window; use [`Self::create`] for that, or [`Self::exists`] to
+    /// check whether it is already running.
+    pub fn new(session: Session, name: String) -> Self {
+        Self { session, name }
+    }
+
+    /// Create a new detached window that runs `command` from the `start_dir` directory.
+    ///
+    /// If the session does not exist yet, it is created along with the window.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - There is a problem executing the `tmux` command.
+    /// - tmux fails to create both the window and the session.
+    pub fn create<P>(
+        session: &Session,
+        name: &str,
+        start_dir: P,
+        command: &str,
+    ) -> Result<Self, TmuxError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let start_dir = start_dir.as_ref();
+
+        let status = super::command(session.server())
+            .args([
+                "new-window",
+                "-d",
+                "-t",
+                &session.target(),
+                "-n",
+                name,
+                "-c",
+            ])
+            .arg(start_dir)
+            .arg(command)
+            .status()
+            .map_err(TmuxError::command_failed)?;
+
+        if !status.success() {
+            // The most likely cause is that the session doesn't exist yet.
+            super::run(
+                super::command(session.server())
+                    .args(["new-session", "-d", "-s", session.name(), "-n", name, "-c"])
+                    .arg(start_dir)
+                    .arg(command),
+            )?;
+        }
+
+        Ok(Self::new(session.to_owned(), name.to_owned()))
+    }
+
+    /// Get the session this window belongs to.
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Get the name of the window.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the target string that exactly matches this window (`=session:=window`).
+    ///
+    /// Both the session and the window need the `=` prefix; otherwise, a window named `example`
+    /// would also match a window named `example-2`.
+    pub fn target(&self) -> String {
+        format!("{}:={}", self.session.target(), self.name)
+    }
+
+    /// Check whether the window is currently open.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - There is a problem executing the `tmux` command.
+    pub fn exists(&self) -> Result<bool, TmuxError> {
+        // `has-session` only checks the session part of the target, so look for the window name
+        // in the session's list of windows instead.
+        let output = super::command(self.session.server())
+            .args([
+                "list-windows",
+                "-t",
+                &self.session.target(),
+                "-F",
+                "#{window_name}",
+            ])
+            .stdout(std::process::Stdio::piped())
+            .output()
+            .map_err(TmuxError::command_failed)?;
+
+        // A failure here means the session itself doesn't exist.
+        if !output.status.success() {
+            return Ok(false);
+        }
+
+        let exists = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .any(|name| name == self.name);
+
+        Ok(exists)
+    }
+
+    /// Send keys into the window, as if they were typed by the user.
+    ///
+    /// Each key is interpreted by tmux, so special keys like `C-c` or `Enter` are supported.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - There is a problem executing the `tmux` command.
+    /// - The window does not exist.
+    pub fn send_keys(&self, keys: &[&str]) -> Result<(), TmuxError> {
+        super::run(
+            super::command(self.session.server())
+                .args(["send-keys", "-t", &self.target()])
+                .args(keys),
+        )
+    }
+
+    /// Get the process ID of the program running in the window's pane.
+    ///
+    /// This is the process tmux started for the window (e.g., the shell running `start.sh`), not
+    /// necessarily the process you are interested in.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - There is a problem executing the `tmux` command.
+    /// - The window does not exist.
+    pub fn pane_pid(&self) -> Result<u32, TmuxError> {
+        let output = super::command(self.session.server())
+            .args(["list-panes", "-t", &self.target(), "-F", "#{pane_pid}"])
+            .stdout(std::process::Stdio::piped())
+            .output()
+            .map_err(TmuxError::command_failed)?;
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .and_then(|line| line.trim().parse().ok())
+            .filter(|_| output.status.success())
+            .ok_or_else(|| TmuxError::WindowNotFound {
+                target: self.target(),
+            })
+    }
+
+    /// Get the text currently displayed in the window.
+    ///
+    /// By default, only the visible part of the pane is returned. Set `history` to also include
+    /// that many lines of scrollback from above the visible area.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - There is a problem executing the `tmux` command.
+    /// - The window does not exist.
+    pub fn capture_pane(&self, history: Option<usize>) -> Result<String, TmuxError> {
+        if !self.exists()? {
+            return Err(TmuxError::WindowNotFound {
+                target: self.target(),
+            });
+        }
+
+        let mut command = super::command(self.session.server());
+        command.args(["capture-pane", "-p", "-t", &self.target()]);
+
+        if let Some(lines) = history {
+            command.args(["-S", &format!("-{lines}")]);
+        }
+
+        let output = command
+            .stdout(std::process::Stdio::piped())
+            .output()
+            .map_err(TmuxError::command_failed)?;
+
+        if !output.status.success() {
+            return Err(TmuxError::ExitFailure {
+                status: output.status,
+            });
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Connect the current terminal to the window, returning once the user detaches.
+    ///
+    /// If this is called from inside a client of the same tmux server, the client is switched
+    /// to the window instead, and this returns immediately. From inside a different tmux server,
+    /// the window is attached as a nested session.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - There is a problem executing the `tmux` command.
+    /// - The window does not exist.
+    pub fn attach(&self) -> Result<(), TmuxError> {
+        let current_server = std::env::var("TMUX").ok();
+        let mut command = super::command(self.session.server());
+
+        match current_server.as_deref().and_then(socket_name) {
+            Some(name) if name == self.session.server() => {
+                command.args(["switch-client", "-t", &self.target()]);
+            }
+            _ => {
+                // tmux refuses to attach from inside of another tmux client unless `TMUX` is unset.
+                command
+                    .args(["attach-session", "-t", &self.target()])
+                    .env_remove("TMUX");
+            }
+        }
+
+        // The user needs to see and interact with the console, so don't discard its output.
+        super::run(
+            command
+                .stdout(std::process::Stdio::inherit())
+                .stderr(std::process::Stdio::inherit()),
+        )
+    }
+
+    /// Close the window, terminating whatever is running inside of it.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    ///
+    /// - There is a problem executing the `tmux` command.
+    /// - The window does not exist.
+    pub fn kill(&self) -> Result<(), TmuxError> {
+        super::run(super::command(self.session.server()).args([
+            "kill-window",
+            "-t",
+            &self.target(),
+        ]))
+    }
+}
+
+/// Get the socket name from the value of the `TMUX` environment variable.
+///
+/// tmux sets `TMUX` to `socket_path,pid,session_index` for the programs running inside of it,
+/// and the socket name (the value passed to `tmux -L`) is the last part of the socket path.
+fn socket_name(tmux: &str) -> Option<&str> {
+    let socket_path = tmux.split(',').next()?;
+    std::path::Path::new(socket_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_socket_name() {
+        assert_eq!(socket_name("/tmp/tmux-1000/axiom,4242,0"), Some("axiom"));
+        assert_eq!(socket_name("/tmp/tmux-1000/default,1,3"), Some("default"));
+        assert_eq!(socket_name(""), None);
+    }
+}